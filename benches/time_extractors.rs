@@ -0,0 +1,63 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use discord_server_manager::{
+    extractor::Extractor,
+    time_converter::{
+        extractor::{OverlapResolver, Prefilter, TimeExtractorContext},
+        model::{ExtractedTime, TimeComponents, TimeKind},
+        ExtractorDefinitions,
+    },
+};
+
+type TimeResolver = OverlapResolver<TimeExtractorContext<Tz>, ExtractedTime>;
+
+fn corpus() -> Vec<String> {
+    include_str!("corpus.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.replace("\\n", "\n"))
+        .collect()
+}
+
+fn resolver(definitions: &ExtractorDefinitions, prefilter: bool) -> TimeResolver {
+    let mut resolver = OverlapResolver::new();
+    for (priority, extractor) in definitions.time_extractors(true) {
+        resolver = resolver.with(priority, extractor);
+    }
+
+    match definitions.prefilter() {
+        Some(patterns) if prefilter => resolver.prefilter(Prefilter::new(patterns).unwrap()),
+        _ => resolver,
+    }
+}
+
+fn bench_time_extractors(c: &mut Criterion) {
+    let definitions = ExtractorDefinitions::load(None).unwrap();
+    let corpus = corpus();
+
+    let timestamp = DateTime::from_utc(NaiveDate::from_ymd(2021, 3, 4).and_hms(9, 0, 0), Utc);
+    let ctx = TimeExtractorContext::new(
+        Tz::UTC,
+        TimeComponents::new(9, 0, TimeKind::Military).unwrap(),
+    )
+    .with_date(NaiveDate::from_ymd(2021, 3, 4))
+    .with_message_timestamp(timestamp);
+
+    let mut group = c.benchmark_group("time_extractors");
+    for (name, prefilter) in [("without_prefilter", false), ("with_prefilter", true)] {
+        let resolver = resolver(&definitions, prefilter);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for message in &corpus {
+                    black_box(resolver.extract_matches(black_box(message), &ctx));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_time_extractors);
+criterion_main!(benches);
@@ -1,11 +1,17 @@
 use serenity::{
     async_trait,
     client::{Context, EventHandler},
-    model::{channel::Message, event::GuildMemberUpdateEvent},
+    model::{
+        channel::Message,
+        event::{GuildMemberUpdateEvent, MessageUpdateEvent},
+        gateway::Ready,
+        interactions::Interaction,
+    },
 };
 
 type Handler = Box<dyn EventHandler>;
 
+#[derive(Default)]
 pub struct CompositeEventHandler {
     handlers: Vec<Handler>,
 }
@@ -41,4 +47,24 @@ impl EventHandler for CompositeEventHandler {
             handler.message(ctx.clone(), msg.clone()).await
         }
     }
+
+    async fn message_update(&self, ctx: Context, event: MessageUpdateEvent) {
+        for handler in &self.handlers {
+            handler.message_update(ctx.clone(), event.clone()).await
+        }
+    }
+
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        for handler in &self.handlers {
+            handler.ready(ctx.clone(), ready.clone()).await
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        for handler in &self.handlers {
+            handler
+                .interaction_create(ctx.clone(), interaction.clone())
+                .await
+        }
+    }
 }
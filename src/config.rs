@@ -1,13 +1,30 @@
-use std::{collections::HashSet, env};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    str::FromStr,
+};
 
 use chrono_tz::Tz;
 use log::debug;
-use serenity::model::id::RoleId;
+use serenity::model::id::{ChannelId, GuildId, RoleId};
+
+/// The colour of embeds in guilds without their own colour.
+const DEFAULT_EMBED_COLOUR: u32 = 0x5865F2;
 
 #[derive(Debug, Clone)]
 pub struct Config {
     bot_token: String,
     location_roles: HashSet<LocationRole>,
+    time_output_mode: TimeOutputMode,
+    response_style: ResponseStyle,
+    day_shift_format: DayShiftFormat,
+    guild_colours: HashMap<GuildId, u32>,
+    infer_time_kind: bool,
+    masked_markdown: HashSet<MarkdownRegion>,
+    guild_locales: HashMap<GuildId, Locale>,
+    channel_locales: HashMap<ChannelId, Locale>,
+    extractors_file: Option<String>,
+    output_zones_file: String,
 }
 
 impl Config {
@@ -23,12 +40,125 @@ impl Config {
         debug!("LOCATION_ROLES={}", bot_token);
 
         let location_roles = Config::parse_location_roles(&location_roles_str);
+
+        let time_output_mode = env::var("TIME_OUTPUT_MODE")
+            .map(|mode| mode.parse().expect("Invalid time output mode"))
+            .unwrap_or(TimeOutputMode::FixedZones);
+
+        debug!("TIME_OUTPUT_MODE={:?}", time_output_mode);
+
+        let response_style = env::var("RESPONSE_STYLE")
+            .map(|style| style.parse().expect("Invalid response style"))
+            .unwrap_or(ResponseStyle::Embed);
+
+        debug!("RESPONSE_STYLE={:?}", response_style);
+
+        let day_shift_format = env::var("DAY_SHIFT_FORMAT")
+            .map(|format| format.parse().expect("Invalid day shift format"))
+            .unwrap_or(DayShiftFormat::Offset);
+
+        debug!("DAY_SHIFT_FORMAT={:?}", day_shift_format);
+
+        let guild_colours = env::var("GUILD_COLOURS")
+            .map(|colours| Config::parse_guild_colours(&colours))
+            .unwrap_or_default();
+
+        debug!("GUILD_COLOURS={:?}", guild_colours);
+
+        let infer_time_kind = env::var("INFER_TIME_KIND")
+            .map(|infer| infer.trim().parse().expect("Invalid infer time kind flag"))
+            .unwrap_or(false);
+
+        debug!("INFER_TIME_KIND={}", infer_time_kind);
+
+        let masked_markdown = env::var("MASKED_MARKDOWN")
+            .map(|regions| Config::parse_markdown_regions(&regions))
+            .unwrap_or_else(|_| MarkdownRegion::ALL.iter().copied().collect());
+
+        debug!("MASKED_MARKDOWN={:?}", masked_markdown);
+
+        let guild_locales: HashMap<GuildId, Locale> = env::var("GUILD_LOCALES")
+            .map(|locales| Config::parse_locales(&locales))
+            .unwrap_or_default();
+
+        debug!("GUILD_LOCALES={:?}", guild_locales);
+
+        let channel_locales: HashMap<ChannelId, Locale> = env::var("CHANNEL_LOCALES")
+            .map(|locales| Config::parse_locales(&locales))
+            .unwrap_or_default();
+
+        debug!("CHANNEL_LOCALES={:?}", channel_locales);
+
+        let extractors_file = env::var("EXTRACTORS_FILE").ok();
+
+        debug!("EXTRACTORS_FILE={:?}", extractors_file);
+
+        let output_zones_file =
+            env::var("OUTPUT_ZONES_FILE").unwrap_or_else(|_| String::from("output_zones.json"));
+
+        debug!("OUTPUT_ZONES_FILE={}", output_zones_file);
+
         Config {
             bot_token,
             location_roles,
+            time_output_mode,
+            response_style,
+            day_shift_format,
+            guild_colours,
+            infer_time_kind,
+            masked_markdown,
+            guild_locales,
+            channel_locales,
+            extractors_file,
+            output_zones_file,
         }
     }
 
+    /// Parses a list of locales for guilds or channels, in the same `locale:id` form as
+    /// the location roles, e.g. `nl:1234,de:5678`.
+    fn parse_locales<Id: From<u64> + Eq + std::hash::Hash>(text: &str) -> HashMap<Id, Locale> {
+        text.split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                let (locale, id) = entry
+                    .split_once(':')
+                    .expect("Invalid locale entry, expected locale:id");
+                let locale: Locale = locale.parse().expect("Invalid locale");
+                let id: u64 = id.trim().parse().expect("Invalid locale id");
+                (Id::from(id), locale)
+            })
+            .collect()
+    }
+
+    /// Parses a list of embed colours for guilds, as hex RGB colours in the same
+    /// `colour:id` form as the locales, e.g. `ff8800:1234,#00aaff:5678`.
+    fn parse_guild_colours(text: &str) -> HashMap<GuildId, u32> {
+        text.split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                let (colour, id) = entry
+                    .split_once(':')
+                    .expect("Invalid guild colour entry, expected colour:id");
+                let colour = u32::from_str_radix(colour.trim().trim_start_matches('#'), 16)
+                    .ok()
+                    .filter(|colour| *colour <= 0xFF_FF_FF)
+                    .expect("Invalid guild colour, expected a hex RGB colour");
+                let id: u64 = id.trim().parse().expect("Invalid guild colour id");
+                (GuildId(id), colour)
+            })
+            .collect()
+    }
+
+    fn parse_markdown_regions(text: &str) -> HashSet<MarkdownRegion> {
+        if text.trim().eq_ignore_ascii_case("none") {
+            return HashSet::new();
+        }
+
+        text.split(',')
+            .map(|region| region.parse().expect("Invalid markdown region"))
+            .collect()
+    }
+
     fn parse_location_roles(text: &str) -> HashSet<LocationRole> {
         text.split(',')
             .filter_map(|location_role_str| {
@@ -52,6 +182,224 @@ impl Config {
     pub fn location_roles(&self) -> &HashSet<LocationRole> {
         &self.location_roles
     }
+
+    pub fn time_output_mode(&self) -> TimeOutputMode {
+        self.time_output_mode
+    }
+
+    /// How replies with converted times are laid out.
+    pub fn response_style(&self) -> ResponseStyle {
+        self.response_style
+    }
+
+    /// How converted times show that they're on a different day than the author's.
+    pub fn day_shift_format(&self) -> DayShiftFormat {
+        self.day_shift_format
+    }
+
+    /// The colour of the embeds in replies in a guild.
+    pub fn embed_colour(&self, guild_id: GuildId) -> u32 {
+        self.guild_colours
+            .get(&guild_id)
+            .copied()
+            .unwrap_or(DEFAULT_EMBED_COLOUR)
+    }
+
+    /// Whether bare hours without AM/PM ("at 8") are converted, with AM/PM inferred.
+    pub fn infer_time_kind(&self) -> bool {
+        self.infer_time_kind
+    }
+
+    /// The parts of a message that times aren't converted in.
+    pub fn masked_markdown(&self) -> &HashSet<MarkdownRegion> {
+        &self.masked_markdown
+    }
+
+    /// The file that the time and date extractors are defined in, if they aren't the
+    /// built-in ones.
+    pub fn extractors_file(&self) -> Option<&str> {
+        self.extractors_file.as_deref()
+    }
+
+    /// The file that the zones each guild converts times to are saved in.
+    pub fn output_zones_file(&self) -> &str {
+        &self.output_zones_file
+    }
+
+    /// The locale that times are extracted in for a channel. A channel's own locale takes
+    /// precedence over its guild's, and everywhere else uses English.
+    pub fn locale(&self, guild_id: GuildId, channel_id: ChannelId) -> Locale {
+        self.channel_locales
+            .get(&channel_id)
+            .or_else(|| self.guild_locales.get(&guild_id))
+            .copied()
+            .unwrap_or(Locale::English)
+    }
+}
+
+/// How converted times are presented in replies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeOutputMode {
+    /// A table of the time in each of a fixed set of zones.
+    FixedZones,
+    /// Discord timestamp markup, which every reader sees in their own zone.
+    Native,
+    /// Discord timestamp markup followed by the fixed zone table.
+    Both,
+}
+
+impl TimeOutputMode {
+    pub fn includes_fixed_zones(&self) -> bool {
+        matches!(self, TimeOutputMode::FixedZones | TimeOutputMode::Both)
+    }
+
+    pub fn includes_native(&self) -> bool {
+        matches!(self, TimeOutputMode::Native | TimeOutputMode::Both)
+    }
+}
+
+impl FromStr for TimeOutputMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fixed_zones" => Ok(TimeOutputMode::FixedZones),
+            "native" => Ok(TimeOutputMode::Native),
+            "both" => Ok(TimeOutputMode::Both),
+            other => Err(format!(
+                "Unknown time output mode [{}], expected one of fixed_zones, native or both",
+                other
+            )),
+        }
+    }
+}
+
+/// How replies with converted times are laid out, when they include the fixed zones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseStyle {
+    /// An embed for each time, with a field for each zone.
+    Embed,
+    /// A code block for each time, with a line for each zone.
+    CodeBlock,
+}
+
+impl FromStr for ResponseStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "embed" => Ok(ResponseStyle::Embed),
+            "code_block" => Ok(ResponseStyle::CodeBlock),
+            other => Err(format!(
+                "Unknown response style [{}], expected one of embed or code_block",
+                other
+            )),
+        }
+    }
+}
+
+/// How a converted time that's on a different day than the author's local time is marked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayShiftFormat {
+    /// The number of days ahead or behind, e.g. "+1 day".
+    Offset,
+    /// The day of the week, e.g. "Thu".
+    Weekday,
+    /// No marker.
+    Hidden,
+}
+
+impl FromStr for DayShiftFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "offset" => Ok(DayShiftFormat::Offset),
+            "weekday" => Ok(DayShiftFormat::Weekday),
+            "none" => Ok(DayShiftFormat::Hidden),
+            other => Err(format!(
+                "Unknown day shift format [{}], expected one of offset, weekday or none",
+                other
+            )),
+        }
+    }
+}
+
+/// Parts of a message, marked up using markdown, that times can be ignored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkdownRegion {
+    CodeBlocks,
+    InlineCode,
+    Quotes,
+    Links,
+    Spoilers,
+}
+
+impl MarkdownRegion {
+    pub const ALL: [MarkdownRegion; 5] = [
+        MarkdownRegion::CodeBlocks,
+        MarkdownRegion::InlineCode,
+        MarkdownRegion::Quotes,
+        MarkdownRegion::Links,
+        MarkdownRegion::Spoilers,
+    ];
+}
+
+impl FromStr for MarkdownRegion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "code_blocks" => Ok(MarkdownRegion::CodeBlocks),
+            "inline_code" => Ok(MarkdownRegion::InlineCode),
+            "quotes" => Ok(MarkdownRegion::Quotes),
+            "links" => Ok(MarkdownRegion::Links),
+            "spoilers" => Ok(MarkdownRegion::Spoilers),
+            other => Err(format!(
+                "Unknown markdown region [{}], expected one of code_blocks, inline_code, quotes, links or spoilers",
+                other
+            )),
+        }
+    }
+}
+
+/// Languages that times can be written in. English is always understood, and other
+/// locales add the ways times are written in their language on top of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    English,
+    Dutch,
+    German,
+    French,
+    Spanish,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 5] = [
+        Locale::English,
+        Locale::Dutch,
+        Locale::German,
+        Locale::French,
+        Locale::Spanish,
+    ];
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "en" => Ok(Locale::English),
+            "nl" => Ok(Locale::Dutch),
+            "de" => Ok(Locale::German),
+            "fr" => Ok(Locale::French),
+            "es" => Ok(Locale::Spanish),
+            other => Err(format!(
+                "Unknown locale [{}], expected one of en, nl, de, fr or es",
+                other
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
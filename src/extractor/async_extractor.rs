@@ -0,0 +1,281 @@
+use std::fmt::Debug;
+
+use once_cell::sync::OnceCell;
+use serenity::async_trait;
+use tokio::{
+    runtime::{self, Handle, Runtime},
+    task,
+};
+
+use super::{Chain, Extraction, Extractor};
+
+/// Extracts values of type `R` from a text, using a context of type `C`, where extracting
+/// can wait on something else, such as a cache, a database or an HTTP request.
+///
+/// Synchronous extractors become asynchronous ones with [`Extractor::into_async`], so both
+/// kinds can be combined into one extractor. Asynchronous extractors can in turn be used where
+/// a synchronous one is expected with [`AsyncExtractor::blocking`].
+#[async_trait]
+pub trait AsyncExtractor<C, R>: Send + Sync + Debug
+where
+    C: Sync,
+    R: Send + 'static,
+{
+    async fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>>;
+
+    async fn extract(&self, text: &str, ctx: &C) -> Vec<R> {
+        self.extract_matches(text, ctx)
+            .await
+            .into_iter()
+            .map(Extraction::into_value)
+            .collect()
+    }
+
+    /// Extracts with this extractor, followed by the other extractor.
+    fn chain<E>(self, other: E) -> Chain<Self, E>
+    where
+        Self: Sized,
+        E: AsyncExtractor<C, R>,
+    {
+        Chain::new(self, other)
+    }
+
+    /// Extracts synchronously, by blocking the current thread until extracting is done.
+    fn blocking(self) -> Blocking<Self>
+    where
+        Self: Sized,
+    {
+        Blocking::new(self)
+    }
+}
+
+#[async_trait]
+impl<C, R, E> AsyncExtractor<C, R> for Box<E>
+where
+    C: Sync,
+    R: Send + 'static,
+    E: AsyncExtractor<C, R> + ?Sized,
+{
+    async fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.as_ref().extract_matches(text, ctx).await
+    }
+}
+
+/// Extracts with each of the extractors in order, waiting for each one before starting the
+/// next.
+#[async_trait]
+impl<C, R, E> AsyncExtractor<C, R> for Vec<E>
+where
+    C: Sync,
+    R: Send + 'static,
+    E: AsyncExtractor<C, R>,
+{
+    async fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        let mut extractions = Vec::new();
+        for extractor in self {
+            extractions.extend(extractor.extract_matches(text, ctx).await);
+        }
+        extractions
+    }
+}
+
+#[async_trait]
+impl<C, R, A, B> AsyncExtractor<C, R> for Chain<A, B>
+where
+    C: Sync,
+    R: Send + 'static,
+    A: AsyncExtractor<C, R>,
+    B: AsyncExtractor<C, R>,
+{
+    async fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        let (first, second) = self.parts();
+        let mut extractions = first.extract_matches(text, ctx).await;
+        extractions.extend(second.extract_matches(text, ctx).await);
+        extractions
+    }
+}
+
+/// Runs a synchronous extractor as an asynchronous one, created by [`Extractor::into_async`].
+#[derive(Debug)]
+pub struct IntoAsync<E> {
+    extractor: E,
+}
+
+impl<E> IntoAsync<E> {
+    pub(super) fn new(extractor: E) -> Self {
+        Self { extractor }
+    }
+}
+
+#[async_trait]
+impl<C, R, E> AsyncExtractor<C, R> for IntoAsync<E>
+where
+    C: Sync,
+    R: Send + 'static,
+    E: Extractor<C, R>,
+{
+    async fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.extractor.extract_matches(text, ctx)
+    }
+}
+
+/// Runs an asynchronous extractor as a synchronous one, created by
+/// [`AsyncExtractor::blocking`].
+///
+/// Inside a Tokio runtime, the runtime has to be multi-threaded, since the current thread is
+/// blocked while extracting. Outside of a runtime, a runtime is started the first time it's
+/// needed and kept for later extractions.
+#[derive(Debug)]
+pub struct Blocking<E> {
+    extractor: E,
+    runtime: OnceCell<Runtime>,
+}
+
+impl<E> Blocking<E> {
+    fn new(extractor: E) -> Self {
+        Self {
+            extractor,
+            runtime: OnceCell::new(),
+        }
+    }
+}
+
+impl<C, R, E> Extractor<C, R> for Blocking<E>
+where
+    C: Sync,
+    R: Send + 'static,
+    E: AsyncExtractor<C, R>,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        let extraction = self.extractor.extract_matches(text, ctx);
+        match Handle::try_current() {
+            // The runtime's other threads keep driving its timers and IO while this thread
+            // waits, so the extraction can still use them.
+            Ok(handle) => task::block_in_place(|| handle.block_on(extraction)),
+            Err(_) => self
+                .runtime
+                .get_or_init(|| {
+                    runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("Expected a runtime to be started.")
+                })
+                .block_on(extraction),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, time::Duration};
+
+    use regex::Regex;
+    use tokio::{sync::RwLock, time};
+
+    use super::*;
+
+    /// Extracts the numbers in a text.
+    #[derive(Debug)]
+    struct NumberExtractor;
+
+    impl Extractor<(), u32> for NumberExtractor {
+        fn extract_matches(&self, text: &str, _: &()) -> Vec<Extraction<u32>> {
+            Regex::new(r"\d+")
+                .unwrap()
+                .find_iter(text)
+                .map(|found| {
+                    Extraction::new(
+                        found.range(),
+                        found.as_str(),
+                        found.as_str().parse().unwrap(),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /// Extracts the words in a text that have a number, which are looked up behind a lock
+    /// after a short wait.
+    #[derive(Debug)]
+    struct WordNumberExtractor {
+        numbers: RwLock<HashMap<&'static str, u32>>,
+    }
+
+    impl WordNumberExtractor {
+        fn new() -> Self {
+            let numbers = vec![("three", 3), ("seven", 7)].into_iter().collect();
+            Self {
+                numbers: RwLock::new(numbers),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AsyncExtractor<(), u32> for WordNumberExtractor {
+        async fn extract_matches(&self, text: &str, _: &()) -> Vec<Extraction<u32>> {
+            time::sleep(Duration::from_millis(1)).await;
+            let numbers = self.numbers.read().await;
+            Regex::new(r"[a-z]+")
+                .unwrap()
+                .find_iter(text)
+                .filter_map(|found| {
+                    let number = numbers.get(found.as_str())?;
+                    Some(Extraction::new(found.range(), found.as_str(), *number))
+                })
+                .collect()
+        }
+    }
+
+    const TEXT: &str = "7 apples, three pears and 2 plums";
+
+    #[tokio::test]
+    async fn test_into_async() {
+        let extractor = NumberExtractor.into_async();
+        assert_eq!(extractor.extract(TEXT, &()).await, vec![7, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_async() {
+        let extractor = WordNumberExtractor::new();
+        let spans: Vec<_> = extractor
+            .extract_matches(TEXT, &())
+            .await
+            .iter()
+            .map(Extraction::span)
+            .collect();
+        assert_eq!(spans, vec![10..15]);
+    }
+
+    #[tokio::test]
+    async fn test_chain() {
+        let extractor = NumberExtractor
+            .into_async()
+            .chain(WordNumberExtractor::new());
+        assert_eq!(extractor.extract(TEXT, &()).await, vec![7, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_all() {
+        let extractors: Vec<Box<dyn AsyncExtractor<(), u32>>> = vec![
+            Box::new(WordNumberExtractor::new()),
+            Box::new(NumberExtractor.filter(|number| *number > 5).into_async()),
+        ];
+        assert_eq!(extractors.extract(TEXT, &()).await, vec![3, 7]);
+    }
+
+    #[test]
+    fn test_blocking_without_runtime() {
+        let extractor = WordNumberExtractor::new().blocking();
+        assert_eq!(extractor.extract(TEXT, &()), vec![3]);
+        assert_eq!(extractor.extract("seven", &()), vec![7]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_blocking_in_runtime() {
+        let extractor = WordNumberExtractor::new()
+            .blocking()
+            .chain(NumberExtractor)
+            .dedup_by(|number| *number);
+        assert_eq!(extractor.extract(TEXT, &()), vec![3, 7, 2]);
+    }
+}
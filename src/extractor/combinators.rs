@@ -0,0 +1,346 @@
+use std::{fmt, hash::Hash, marker::PhantomData};
+
+use itertools::Itertools;
+
+use super::{Extraction, Extractor};
+
+/// Maps the values of an extractor, created by [`Extractor::map`].
+pub struct Map<E, F, R> {
+    extractor: E,
+    f: F,
+    value: PhantomData<fn(R) -> R>,
+}
+
+impl<E, F, R> Map<E, F, R> {
+    pub(super) fn new(extractor: E, f: F) -> Self {
+        Self {
+            extractor,
+            f,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<C, R, U, E, F> Extractor<C, U> for Map<E, F, R>
+where
+    E: Extractor<C, R>,
+    F: Fn(R) -> U + Send + Sync,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<U>> {
+        self.extractor
+            .extract_matches(text, ctx)
+            .into_iter()
+            .map(|extraction| extraction.map(&self.f))
+            .collect()
+    }
+}
+
+impl<E: fmt::Debug, F, R> fmt::Debug for Map<E, F, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("extractor", &self.extractor)
+            .finish()
+    }
+}
+
+/// Keeps the values of an extractor that match a predicate, created by [`Extractor::filter`].
+pub struct Filter<E, F> {
+    extractor: E,
+    predicate: F,
+}
+
+impl<E, F> Filter<E, F> {
+    pub(super) fn new(extractor: E, predicate: F) -> Self {
+        Self {
+            extractor,
+            predicate,
+        }
+    }
+}
+
+impl<C, R, E, F> Extractor<C, R> for Filter<E, F>
+where
+    E: Extractor<C, R>,
+    F: Fn(&R) -> bool + Send + Sync,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.extractor
+            .extract_matches(text, ctx)
+            .into_iter()
+            .filter(|extraction| (self.predicate)(extraction.value()))
+            .collect()
+    }
+}
+
+impl<E: fmt::Debug, F> fmt::Debug for Filter<E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("extractor", &self.extractor)
+            .finish()
+    }
+}
+
+/// Extracts with one extractor and then another, created by [`Extractor::chain`].
+#[derive(Debug)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub(super) fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    pub(super) fn parts(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+}
+
+impl<C, R, A, B> Extractor<C, R> for Chain<A, B>
+where
+    A: Extractor<C, R>,
+    B: Extractor<C, R>,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        let mut extractions = self.first.extract_matches(text, ctx);
+        extractions.extend(self.second.extract_matches(text, ctx));
+        extractions
+    }
+}
+
+/// Only keeps the first extraction of each key, created by [`Extractor::dedup_by`].
+pub struct DedupBy<E, F, K> {
+    extractor: E,
+    key: F,
+    key_type: PhantomData<fn() -> K>,
+}
+
+impl<E, F, K> DedupBy<E, F, K> {
+    pub(super) fn new(extractor: E, key: F) -> Self {
+        Self {
+            extractor,
+            key,
+            key_type: PhantomData,
+        }
+    }
+}
+
+impl<C, R, K, E, F> Extractor<C, R> for DedupBy<E, F, K>
+where
+    E: Extractor<C, R>,
+    F: Fn(&R) -> K + Send + Sync,
+    K: Eq + Hash,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.extractor
+            .extract_matches(text, ctx)
+            .into_iter()
+            .unique_by(|extraction| (self.key)(extraction.value()))
+            .collect()
+    }
+}
+
+impl<E: fmt::Debug, F, K> fmt::Debug for DedupBy<E, F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedupBy")
+            .field("extractor", &self.extractor)
+            .finish()
+    }
+}
+
+/// Runs an extractor with a context derived from another context, created by
+/// [`Extractor::with_context`].
+pub struct WithContext<E, F, C> {
+    extractor: E,
+    f: F,
+    context: PhantomData<fn() -> C>,
+}
+
+impl<E, F, C> WithContext<E, F, C> {
+    pub(super) fn new(extractor: E, f: F) -> Self {
+        Self {
+            extractor,
+            f,
+            context: PhantomData,
+        }
+    }
+}
+
+impl<C, D, R, E, F> Extractor<D, R> for WithContext<E, F, C>
+where
+    E: Extractor<C, R>,
+    F: Fn(&D) -> C + Send + Sync,
+{
+    fn extract_matches(&self, text: &str, ctx: &D) -> Vec<Extraction<R>> {
+        self.extractor.extract_matches(text, &(self.f)(ctx))
+    }
+}
+
+impl<E: fmt::Debug, F, C> fmt::Debug for WithContext<E, F, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithContext")
+            .field("extractor", &self.extractor)
+            .finish()
+    }
+}
+
+/// Extracts with the first of the extractors that finds anything, created by [`first_of`].
+pub struct FirstOf<C, R> {
+    extractors: Vec<Box<dyn Extractor<C, R>>>,
+}
+
+impl<C, R> Extractor<C, R> for FirstOf<C, R> {
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.extractors
+            .iter()
+            .map(|extractor| extractor.extract_matches(text, ctx))
+            .find(|extractions| !extractions.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+impl<C, R> fmt::Debug for FirstOf<C, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FirstOf")
+            .field("extractors", &self.extractors)
+            .finish()
+    }
+}
+
+/// Combines extractors so that only the first one that finds anything in a text is used, such
+/// as a specific extractor with a more lenient one as a fallback.
+pub fn first_of<C, R>(extractors: Vec<Box<dyn Extractor<C, R>>>) -> FirstOf<C, R> {
+    FirstOf { extractors }
+}
+
+#[cfg(test)]
+mod test {
+    use regex::Regex;
+
+    use super::*;
+
+    /// Extracts the numbers in a text.
+    #[derive(Debug)]
+    struct NumberExtractor;
+
+    impl Extractor<(), u32> for NumberExtractor {
+        fn extract_matches(&self, text: &str, _: &()) -> Vec<Extraction<u32>> {
+            Regex::new(r"\d+")
+                .unwrap()
+                .find_iter(text)
+                .map(|found| {
+                    Extraction::new(
+                        found.range(),
+                        found.as_str(),
+                        found.as_str().parse().unwrap(),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /// Extracts the words in a text.
+    #[derive(Debug)]
+    struct WordExtractor;
+
+    impl Extractor<(), String> for WordExtractor {
+        fn extract_matches(&self, text: &str, _: &()) -> Vec<Extraction<String>> {
+            Regex::new(r"[a-z]+")
+                .unwrap()
+                .find_iter(text)
+                .map(|found| Extraction::new(found.range(), found.as_str(), found.as_str().into()))
+                .collect()
+        }
+    }
+
+    const TEXT: &str = "7 apples, 3 pears and 7 plums";
+
+    #[test]
+    fn test_map() {
+        let extractor = NumberExtractor.map(|number| number * 2);
+        assert_eq!(extractor.extract(TEXT, &()), vec![14, 6, 14]);
+    }
+
+    #[test]
+    fn test_map_keeps_spans() {
+        let extractor = NumberExtractor.map(|number| number.to_string());
+        let spans: Vec<_> = extractor
+            .extract_matches(TEXT, &())
+            .iter()
+            .map(Extraction::span)
+            .collect();
+        assert_eq!(spans, vec![0..1, 10..11, 22..23]);
+    }
+
+    #[test]
+    fn test_filter() {
+        let extractor = NumberExtractor.filter(|number| *number > 5);
+        assert_eq!(extractor.extract(TEXT, &()), vec![7, 7]);
+    }
+
+    #[test]
+    fn test_chain() {
+        let extractor = NumberExtractor
+            .map(|number| number.to_string())
+            .chain(WordExtractor.filter(|word| word.ends_with('s')));
+        assert_eq!(
+            extractor.extract(TEXT, &()),
+            vec!["7", "3", "7", "apples", "pears", "plums"]
+        );
+    }
+
+    #[test]
+    fn test_dedup_by() {
+        let extractor = NumberExtractor.dedup_by(|number| *number);
+        assert_eq!(extractor.extract(TEXT, &()), vec![7, 3]);
+    }
+
+    #[test]
+    fn test_with_context() {
+        let extractor = NumberExtractor.with_context(|_: &u32| ());
+        assert_eq!(extractor.extract(TEXT, &42), vec![7, 3, 7]);
+    }
+
+    #[test]
+    fn test_first_of() {
+        let extractor = first_of(vec![
+            Box::new(
+                NumberExtractor
+                    .filter(|number| *number > 10)
+                    .map(|n| n.to_string()),
+            ),
+            Box::new(WordExtractor.filter(|word| word.len() == 5)),
+            Box::new(NumberExtractor.map(|number| number.to_string())),
+        ]);
+        assert_eq!(extractor.extract(TEXT, &()), vec!["pears", "plums"]);
+    }
+
+    #[test]
+    fn test_first_of_nothing() {
+        let extractor: FirstOf<(), u32> =
+            first_of(vec![Box::new(NumberExtractor.filter(|_| false))]);
+        assert!(extractor.extract(TEXT, &()).is_empty());
+    }
+
+    #[test]
+    fn test_boxed() {
+        let extractor: Box<dyn Extractor<(), u32>> = Box::new(NumberExtractor);
+        assert_eq!(
+            extractor.dedup_by(|number| *number).extract(TEXT, &()),
+            vec![7, 3]
+        );
+    }
+
+    #[test]
+    fn test_all() {
+        let extractors: Vec<Box<dyn Extractor<(), String>>> = vec![
+            Box::new(NumberExtractor.map(|number| number.to_string())),
+            Box::new(WordExtractor.filter(|word| word.starts_with('p'))),
+        ];
+        assert_eq!(
+            extractors.extract(TEXT, &()),
+            vec!["7", "3", "7", "pears", "plums"]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+mod async_extractor;
+mod combinators;
+
+use std::{fmt::Debug, hash::Hash, ops::Range};
+
+pub use async_extractor::{AsyncExtractor, Blocking, IntoAsync};
+pub use combinators::{first_of, Chain, DedupBy, Filter, FirstOf, Map, WithContext};
+
+/// A value extracted from a text, along with the part of the text it was extracted from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Extraction<R> {
+    span: Range<usize>,
+    text: String,
+    value: R,
+}
+
+#[allow(dead_code)]
+impl<R> Extraction<R> {
+    pub fn new(span: Range<usize>, text: &str, value: R) -> Self {
+        Self {
+            span,
+            text: String::from(text),
+            value,
+        }
+    }
+
+    /// The byte range of the text the value was extracted from.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn value(&self) -> &R {
+        &self.value
+    }
+
+    pub fn into_value(self) -> R {
+        self.value
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(R) -> U) -> Extraction<U> {
+        Extraction {
+            span: self.span,
+            text: self.text,
+            value: f(self.value),
+        }
+    }
+
+    /// Moves the span by an offset, for values extracted from a part of a larger text.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.span = (self.span.start + offset)..(self.span.end + offset);
+        self
+    }
+
+    /// Maps the value, dropping the extraction when there's no mapped value.
+    pub fn and_then<U>(self, f: impl FnOnce(R) -> Option<U>) -> Option<Extraction<U>> {
+        let value = f(self.value)?;
+        Some(Extraction {
+            span: self.span,
+            text: self.text,
+            value,
+        })
+    }
+}
+
+/// Extracts values of type `R` from a text, using a context of type `C`.
+///
+/// Extractors can be combined into new extractors, such as
+/// `times.filter(|time| ...).dedup_by(|time| ...)`, where each extraction keeps the part of
+/// the text it was extracted from.
+pub trait Extractor<C, R>: Send + Sync + Debug {
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>>;
+
+    fn extract(&self, text: &str, ctx: &C) -> Vec<R> {
+        self.extract_matches(text, ctx)
+            .into_iter()
+            .map(Extraction::into_value)
+            .collect()
+    }
+
+    /// Maps each extracted value.
+    fn map<U, F>(self, f: F) -> Map<Self, F, R>
+    where
+        Self: Sized,
+        F: Fn(R) -> U + Send + Sync,
+    {
+        Map::new(self, f)
+    }
+
+    /// Only keeps the extracted values that match the predicate.
+    fn filter<F>(self, predicate: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&R) -> bool + Send + Sync,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Extracts with this extractor, followed by the other extractor.
+    fn chain<E>(self, other: E) -> Chain<Self, E>
+    where
+        Self: Sized,
+        E: Extractor<C, R>,
+    {
+        Chain::new(self, other)
+    }
+
+    /// Only keeps the first extraction of each key, dropping any later extractions of the
+    /// same key.
+    fn dedup_by<K, F>(self, key: F) -> DedupBy<Self, F, K>
+    where
+        Self: Sized,
+        F: Fn(&R) -> K + Send + Sync,
+        K: Eq + Hash,
+    {
+        DedupBy::new(self, key)
+    }
+
+    /// Extracts using a context of another type, which this extractor's context is derived
+    /// from.
+    fn with_context<D, F>(self, f: F) -> WithContext<Self, F, C>
+    where
+        Self: Sized,
+        F: Fn(&D) -> C + Send + Sync,
+    {
+        WithContext::new(self, f)
+    }
+
+    /// Extracts asynchronously, so this extractor can be combined with asynchronous ones.
+    fn into_async(self) -> IntoAsync<Self>
+    where
+        Self: Sized,
+    {
+        IntoAsync::new(self)
+    }
+}
+
+impl<C, R, E> Extractor<C, R> for Box<E>
+where
+    E: Extractor<C, R> + ?Sized,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.as_ref().extract_matches(text, ctx)
+    }
+}
+
+/// Extracts with each of the extractors in order.
+impl<C, R, E> Extractor<C, R> for Vec<E>
+where
+    E: Extractor<C, R>,
+{
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.iter()
+            .flat_map(|extractor| extractor.extract_matches(text, ctx))
+            .collect()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod composite_event_handler;
+pub mod config;
+pub mod extractor;
+pub mod time_converter;
+pub mod user_roles;
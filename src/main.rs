@@ -1,12 +1,8 @@
-mod composite_event_handler;
-mod config;
-mod extractor;
-mod time_converter;
-mod user_roles;
-
 use std::sync::Arc;
 
-use composite_event_handler::CompositeEventHandler;
+use discord_server_manager::{
+    composite_event_handler::CompositeEventHandler, config, time_converter, user_roles,
+};
 use log::info;
 use serenity::{client::bridge::gateway::GatewayIntents, Client};
 
@@ -20,13 +16,29 @@ async fn main() {
     let config = Arc::new(config::Config::load());
     info!("Loaded Configuration: {:?}", &config);
 
+    let extractor_definitions =
+        time_converter::ExtractorDefinitions::load(config.extractors_file())
+            .unwrap_or_else(|error| panic!("Invalid extractor definitions. {}", error));
+    info!("Loaded extractor definitions");
+
     let user_role_cache = Arc::new(user_roles::UserRoleCache::new());
     info!("Created user role cache");
 
+    let output_zones = Arc::new(
+        time_converter::OutputZoneStore::load(Some(config.output_zones_file()))
+            .unwrap_or_else(|error| panic!("Invalid output zones. {}", error)),
+    );
+    info!("Loaded output zones");
+
     let composite_event_handler = CompositeEventHandler::new()
         .event_handler(time_converter::MessageHandler::new(
             config.clone(),
             user_role_cache.clone(),
+            output_zones.clone(),
+            &extractor_definitions,
+        ))
+        .event_handler(time_converter::ZoneCommandHandler::new(
+            output_zones.clone(),
         ))
         .event_handler(user_roles::UserRoleUpdateHandler::new(
             user_role_cache.clone(),
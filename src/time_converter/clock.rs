@@ -0,0 +1,37 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+
+/// A source of the current time, so that conversions can be pinned to any instant.
+pub trait Clock: Send + Sync + Debug {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system's clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that's always at the same instant.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    instant: DateTime<Utc>,
+}
+
+#[allow(dead_code)]
+impl FixedClock {
+    pub fn new(instant: DateTime<Utc>) -> Self {
+        Self { instant }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.instant
+    }
+}
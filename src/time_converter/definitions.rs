@@ -0,0 +1,813 @@
+use std::{collections::HashSet, fs};
+
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::extractor::Extractor;
+
+use super::{
+    extractor::{
+        AmbiguousZonePolicy, CurrentTimeExtractor, DiscordTimestampExtractor, DynamicDateExtractor,
+        DynamicTimeExtractor, ExtractorError, ExtractorResult, FixedDateExtractor,
+        FixedTimeExtractor, InferredTimeExtractor, PhraseTimeExtractor, Prefilter,
+        RelativeTimeExtractor, TimeExtractorContext, TimeRangeExtractor, TIME_ZONE_SUFFIX_PATTERN,
+    },
+    model::{DateComponents, ExtractedTime, TimeComponents},
+};
+
+type TimeExtractor = Box<dyn Extractor<TimeExtractorContext<Tz>, ExtractedTime>>;
+type DateExtractor = Box<dyn Extractor<TimeExtractorContext<Tz>, chrono::NaiveDate>>;
+
+/// The definitions used when no file is configured, which also serve as an example.
+const BUILT_IN_DEFINITIONS: &str = include_str!("extractors.toml");
+
+const ZONE_SUFFIX_PLACEHOLDER: &str = "{zone_suffix}";
+
+const PATTERN_FIELD: &str = "pattern";
+const VALUE_FIELD: &str = "value";
+const PRIORITY_FIELD: &str = "priority";
+const AMBIGUOUS_ZONE_POLICY_FIELD: &str = "ambiguous_zone_policy";
+const DAY_PART_HINTS_FIELD: &str = "day_part_hints";
+
+type DefinitionResult<T> = Result<T, DefinitionError>;
+
+#[derive(Error, Debug)]
+pub enum DefinitionError {
+    #[error("Failed to read extractor definitions from [{path}]: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse extractor definitions: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Extractor [{name}] is defined more than once.")]
+    DuplicateName { name: String },
+    #[error("Extractor [{name}] has unknown kind [{kind}].")]
+    UnknownKind { name: String, kind: String },
+    #[error("Extractor [{name}] of kind [{kind}] is missing the {field} field.")]
+    MissingField {
+        name: String,
+        kind: String,
+        field: &'static str,
+    },
+    #[error("Extractor [{name}] of kind [{kind}] doesn't take the {field} field.")]
+    UnexpectedField {
+        name: String,
+        kind: String,
+        field: &'static str,
+    },
+    #[error("Extractor [{name}] has an invalid {field}: {reason}")]
+    InvalidField {
+        name: String,
+        field: &'static str,
+        reason: String,
+    },
+    #[error("The prefilter is invalid: {0}")]
+    InvalidPrefilter(ExtractorError),
+    #[error("Extractor [{name}] is invalid: {source}")]
+    InvalidExtractor {
+        name: String,
+        source: ExtractorError,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DefinitionFile {
+    prefilter: Option<Vec<String>>,
+    #[serde(rename = "extractor", default)]
+    extractors: Vec<RawDefinition>,
+}
+
+/// An extractor definition as it's written in the file, before it's been checked.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawDefinition {
+    name: String,
+    kind: String,
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    pattern: Option<String>,
+    value: Option<toml::Value>,
+    priority: Option<u32>,
+    ambiguous_zone_policy: Option<String>,
+    day_part_hints: Option<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone)]
+enum Definition {
+    DiscordTimestamp {
+        priority: u32,
+    },
+    TimeRange {
+        priority: u32,
+        pattern: String,
+        policy: AmbiguousZonePolicy,
+    },
+    DynamicTime {
+        priority: u32,
+        pattern: String,
+        policy: AmbiguousZonePolicy,
+    },
+    FixedTime {
+        priority: u32,
+        pattern: String,
+        value: TimeComponents,
+    },
+    CurrentTime {
+        priority: u32,
+        pattern: String,
+    },
+    PhraseTime {
+        priority: u32,
+        pattern: String,
+    },
+    RelativeTime {
+        priority: u32,
+        pattern: String,
+    },
+    InferredTime {
+        priority: u32,
+        pattern: String,
+        day_part_hints: Option<String>,
+    },
+    FixedDate {
+        pattern: String,
+        value: DateComponents,
+    },
+    DynamicDate {
+        pattern: String,
+    },
+}
+
+/// The extractors to find times and dates with, loaded from a file so patterns can be tuned
+/// without rebuilding the bot. Every definition is checked when it's loaded, so a bad
+/// definition is reported at startup.
+#[derive(Debug, Clone)]
+pub struct ExtractorDefinitions {
+    prefilter: Option<Vec<String>>,
+    definitions: Vec<Definition>,
+}
+
+impl ExtractorDefinitions {
+    /// Loads the definitions from a file, or the built-in definitions if there's no file.
+    pub fn load(path: Option<&str>) -> DefinitionResult<Self> {
+        match path {
+            Some(path) => {
+                let text = fs::read_to_string(path).map_err(|source| DefinitionError::Read {
+                    path: String::from(path),
+                    source,
+                })?;
+                Self::parse(&text)
+            }
+            None => Self::parse(BUILT_IN_DEFINITIONS),
+        }
+    }
+
+    pub fn parse(text: &str) -> DefinitionResult<Self> {
+        let file: DefinitionFile = toml::from_str(text)?;
+
+        if let Some(patterns) = &file.prefilter {
+            Prefilter::new(patterns).map_err(DefinitionError::InvalidPrefilter)?;
+        }
+
+        let mut names = HashSet::new();
+        let mut definitions = Vec::new();
+        for raw in file.extractors {
+            if !names.insert(raw.name.clone()) {
+                return Err(DefinitionError::DuplicateName { name: raw.name });
+            }
+
+            // Disabled definitions are still checked, so they can be enabled safely later.
+            let definition = raw.validate()?;
+            if raw.enabled {
+                definitions.push(definition);
+            }
+        }
+
+        Ok(Self {
+            prefilter: file.prefilter,
+            definitions,
+        })
+    }
+
+    /// The patterns that find the parts of a message the time extractors search, if the
+    /// definitions have any. Without them the whole message is searched.
+    pub fn prefilter(&self) -> Option<&[String]> {
+        self.prefilter.as_deref()
+    }
+
+    /// Creates the time extractors along with their priorities. Extractors that infer AM/PM
+    /// are only included when asked for.
+    pub fn time_extractors(&self, include_inferred: bool) -> Vec<(u32, TimeExtractor)> {
+        self.definitions
+            .iter()
+            .filter(|definition| include_inferred || !definition.is_inferred())
+            .filter_map(|definition| {
+                definition
+                    .time_extractor()
+                    .expect("Expected the definition to have been checked when it was loaded.")
+            })
+            .collect()
+    }
+
+    pub fn date_extractors(&self) -> Vec<DateExtractor> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| {
+                definition
+                    .date_extractor()
+                    .expect("Expected the definition to have been checked when it was loaded.")
+            })
+            .collect()
+    }
+}
+
+impl Definition {
+    fn is_inferred(&self) -> bool {
+        matches!(self, Definition::InferredTime { .. })
+    }
+
+    /// Creates the extractor if it's a time extractor.
+    fn time_extractor(&self) -> ExtractorResult<Option<(u32, TimeExtractor)>> {
+        let extractor: (u32, TimeExtractor) = match self {
+            Definition::DiscordTimestamp { priority } => {
+                (*priority, Box::new(DiscordTimestampExtractor::new()))
+            }
+            Definition::TimeRange {
+                priority,
+                pattern,
+                policy,
+            } => (
+                *priority,
+                Box::new(TimeRangeExtractor::new(pattern)?.ambiguous_zone_policy(*policy)),
+            ),
+            Definition::DynamicTime {
+                priority,
+                pattern,
+                policy,
+            } => (
+                *priority,
+                Box::new(DynamicTimeExtractor::new(pattern)?.ambiguous_zone_policy(*policy)),
+            ),
+            Definition::FixedTime {
+                priority,
+                pattern,
+                value,
+            } => (
+                *priority,
+                Box::new(FixedTimeExtractor::new(pattern, *value)?),
+            ),
+            Definition::CurrentTime { priority, pattern } => {
+                (*priority, Box::new(CurrentTimeExtractor::new(pattern)?))
+            }
+            Definition::PhraseTime { priority, pattern } => {
+                (*priority, Box::new(PhraseTimeExtractor::new(pattern)?))
+            }
+            Definition::RelativeTime { priority, pattern } => {
+                (*priority, Box::new(RelativeTimeExtractor::new(pattern)?))
+            }
+            Definition::InferredTime {
+                priority,
+                pattern,
+                day_part_hints,
+            } => {
+                let extractor = InferredTimeExtractor::new(pattern)?;
+                let extractor = match day_part_hints {
+                    Some(hints) => extractor.day_part_hints(hints)?,
+                    None => extractor,
+                };
+                (*priority, Box::new(extractor))
+            }
+            Definition::FixedDate { .. } | Definition::DynamicDate { .. } => return Ok(None),
+        };
+
+        Ok(Some(extractor))
+    }
+
+    /// Creates the extractor if it's a date extractor.
+    fn date_extractor(&self) -> ExtractorResult<Option<DateExtractor>> {
+        let extractor: DateExtractor = match self {
+            Definition::FixedDate { pattern, value } => {
+                Box::new(FixedDateExtractor::new(pattern, *value)?)
+            }
+            Definition::DynamicDate { pattern } => Box::new(DynamicDateExtractor::new(pattern)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(extractor))
+    }
+}
+
+impl RawDefinition {
+    fn validate(&self) -> DefinitionResult<Definition> {
+        let definition = match self.kind.as_str() {
+            "discord_timestamp" => {
+                self.expect_fields(&[PRIORITY_FIELD], &[])?;
+                Definition::DiscordTimestamp {
+                    priority: self.priority(),
+                }
+            }
+            "time_range" => {
+                self.expect_fields(
+                    &[PATTERN_FIELD, PRIORITY_FIELD],
+                    &[AMBIGUOUS_ZONE_POLICY_FIELD],
+                )?;
+                Definition::TimeRange {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                    policy: self.ambiguous_zone_policy()?,
+                }
+            }
+            "dynamic_time" => {
+                self.expect_fields(
+                    &[PATTERN_FIELD, PRIORITY_FIELD],
+                    &[AMBIGUOUS_ZONE_POLICY_FIELD],
+                )?;
+                Definition::DynamicTime {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                    policy: self.ambiguous_zone_policy()?,
+                }
+            }
+            "fixed_time" => {
+                self.expect_fields(&[PATTERN_FIELD, PRIORITY_FIELD, VALUE_FIELD], &[])?;
+                Definition::FixedTime {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                    value: self.fixed_time()?,
+                }
+            }
+            "current_time" => {
+                self.expect_fields(&[PATTERN_FIELD, PRIORITY_FIELD], &[])?;
+                Definition::CurrentTime {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                }
+            }
+            "phrase_time" => {
+                self.expect_fields(&[PATTERN_FIELD, PRIORITY_FIELD], &[])?;
+                Definition::PhraseTime {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                }
+            }
+            "relative_time" => {
+                self.expect_fields(&[PATTERN_FIELD, PRIORITY_FIELD], &[])?;
+                Definition::RelativeTime {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                }
+            }
+            "inferred_time" => {
+                self.expect_fields(&[PATTERN_FIELD, PRIORITY_FIELD], &[DAY_PART_HINTS_FIELD])?;
+                Definition::InferredTime {
+                    priority: self.priority(),
+                    pattern: self.pattern(),
+                    day_part_hints: self.day_part_hints.clone(),
+                }
+            }
+            "fixed_date" => {
+                self.expect_fields(&[PATTERN_FIELD, VALUE_FIELD], &[])?;
+                Definition::FixedDate {
+                    pattern: self.pattern(),
+                    value: self.fixed_date()?,
+                }
+            }
+            "dynamic_date" => {
+                self.expect_fields(&[PATTERN_FIELD], &[])?;
+                Definition::DynamicDate {
+                    pattern: self.pattern(),
+                }
+            }
+            other => {
+                return Err(DefinitionError::UnknownKind {
+                    name: self.name.clone(),
+                    kind: String::from(other),
+                })
+            }
+        };
+
+        // Creating the extractor checks the pattern and its capture groups.
+        definition
+            .time_extractor()
+            .and_then(|_| definition.date_extractor())
+            .map_err(|source| DefinitionError::InvalidExtractor {
+                name: self.name.clone(),
+                source,
+            })?;
+
+        Ok(definition)
+    }
+
+    /// Checks that the definition has every required field for its kind, and no fields that
+    /// its kind doesn't take.
+    fn expect_fields(
+        &self,
+        required: &[&'static str],
+        optional: &[&'static str],
+    ) -> DefinitionResult<()> {
+        let fields = [
+            (PATTERN_FIELD, self.pattern.is_some()),
+            (VALUE_FIELD, self.value.is_some()),
+            (PRIORITY_FIELD, self.priority.is_some()),
+            (
+                AMBIGUOUS_ZONE_POLICY_FIELD,
+                self.ambiguous_zone_policy.is_some(),
+            ),
+            (DAY_PART_HINTS_FIELD, self.day_part_hints.is_some()),
+        ];
+
+        for (field, is_present) in fields.iter().copied() {
+            if !is_present && required.contains(&field) {
+                return Err(DefinitionError::MissingField {
+                    name: self.name.clone(),
+                    kind: self.kind.clone(),
+                    field,
+                });
+            }
+
+            if is_present && !required.contains(&field) && !optional.contains(&field) {
+                return Err(DefinitionError::UnexpectedField {
+                    name: self.name.clone(),
+                    kind: self.kind.clone(),
+                    field,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn invalid(&self, field: &'static str, reason: String) -> DefinitionError {
+        DefinitionError::InvalidField {
+            name: self.name.clone(),
+            field,
+            reason,
+        }
+    }
+
+    // Only called for kinds that require a priority, which is checked beforehand.
+    fn priority(&self) -> u32 {
+        self.priority.unwrap_or_default()
+    }
+
+    fn value_text(&self) -> String {
+        self.value
+            .as_ref()
+            .map(|value| value.to_string())
+            .unwrap_or_default()
+    }
+
+    fn pattern(&self) -> String {
+        self.pattern
+            .as_deref()
+            .unwrap_or_default()
+            .replace(ZONE_SUFFIX_PLACEHOLDER, &TIME_ZONE_SUFFIX_PATTERN)
+    }
+
+    fn ambiguous_zone_policy(&self) -> DefinitionResult<AmbiguousZonePolicy> {
+        match &self.ambiguous_zone_policy {
+            Some(policy) => policy
+                .parse()
+                .map_err(|reason| self.invalid(AMBIGUOUS_ZONE_POLICY_FIELD, reason)),
+            None => Ok(AmbiguousZonePolicy::PreferFirst),
+        }
+    }
+
+    fn fixed_time(&self) -> DefinitionResult<TimeComponents> {
+        let value = self.value.as_ref().and_then(|value| value.as_str());
+        value
+            .and_then(|value| NaiveTime::parse_from_str(value, "%H:%M").ok())
+            .map(TimeComponents::from)
+            .ok_or_else(|| {
+                self.invalid(
+                    VALUE_FIELD,
+                    format!(
+                        "expected a time such as \"13:30\" but was {}",
+                        self.value_text()
+                    ),
+                )
+            })
+    }
+
+    fn fixed_date(&self) -> DefinitionResult<DateComponents> {
+        let value = self.value.as_ref().and_then(|value| value.as_integer());
+        value.map(DateComponents::RelativeDays).ok_or_else(|| {
+            self.invalid(
+                VALUE_FIELD,
+                format!(
+                    "expected a number of days from today but was {}",
+                    self.value_text()
+                ),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    use crate::time_converter::{extractor::OverlapResolver, model::TimeKind};
+
+    use super::*;
+
+    #[test]
+    fn test_built_in_definitions() {
+        let definitions = ExtractorDefinitions::load(None).unwrap();
+        assert_eq!(definitions.time_extractors(false).len(), 13);
+        assert_eq!(definitions.time_extractors(true).len(), 14);
+        assert_eq!(definitions.date_extractors().len(), 7);
+        assert!(definitions.prefilter().is_some());
+    }
+
+    // The prefilter only makes searching faster, so it mustn't change what's found.
+    #[test]
+    fn test_prefilter_keeps_corpus_times() {
+        let definitions = ExtractorDefinitions::load(None).unwrap();
+        let prefilter = Prefilter::new(definitions.prefilter().unwrap()).unwrap();
+
+        let mut resolver = OverlapResolver::new();
+        let mut prefiltered_resolver = OverlapResolver::new().prefilter(prefilter);
+        for (priority, extractor) in definitions.time_extractors(true) {
+            resolver = resolver.with(priority, extractor);
+        }
+        for (priority, extractor) in definitions.time_extractors(true) {
+            prefiltered_resolver = prefiltered_resolver.with(priority, extractor);
+        }
+
+        let timestamp = DateTime::from_utc(NaiveDate::from_ymd(2021, 3, 4).and_hms(9, 0, 0), Utc);
+        let ctx = TimeExtractorContext::new(Tz::UTC, TimeComponents::of(9, 0, TimeKind::Military))
+            .with_date(NaiveDate::from_ymd(2021, 3, 4))
+            .with_message_timestamp(timestamp);
+
+        let corpus = include_str!("../../benches/corpus.txt")
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.replace("\\n", "\n"));
+
+        let mut found = 0;
+        for message in corpus {
+            let expected = resolver.extract_matches(&message, &ctx);
+            let actual = prefiltered_resolver.extract_matches(&message, &ctx);
+            assert_eq!(actual, expected, "{}", message);
+            found += expected.len();
+        }
+        assert!(found > 0);
+    }
+
+    fn instant(hour: u32, minute: u32) -> DateTime<Utc> {
+        DateTime::from_utc(
+            NaiveDate::from_ymd(2021, 3, 4).and_hms(hour, minute, 0),
+            Utc,
+        )
+    }
+
+    macro_rules! test_built_in_extract_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let definitions = ExtractorDefinitions::load(None).unwrap();
+                let mut resolver = OverlapResolver::new();
+                for (priority, extractor) in definitions.time_extractors(true) {
+                    resolver = resolver.with(priority, extractor);
+                }
+
+                let ctx = TimeExtractorContext::new(Tz::UTC, TimeComponents::of(9, 0, TimeKind::Military))
+                    .with_date(NaiveDate::from_ymd(2021, 3, 4))
+                    .with_message_timestamp(instant(9, 0));
+                let actual: Vec<ExtractedTime> = resolver.extract($input_text, &ctx);
+                let expected: Vec<ExtractedTime> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_built_in_extract_data! {
+        test_built_in_range_of_numbers_is_not_an_offset {
+            text: "I'm free from 5 to 9 tonight",
+            expected: vec![ExtractedTime::from(instant(21, 0))],
+        },
+        // Only the midnight itself is converted, rather than 11:45pm on the wrong day.
+        test_built_in_quarter_to_midnight {
+            text: "see you at quarter to 12am",
+            expected: vec![ExtractedTime::from(instant(0, 0))],
+        },
+    }
+
+    #[test]
+    fn test_without_prefilter() {
+        let definitions = ExtractorDefinitions::parse(
+            r#"
+            [[extractor]]
+            name = "today"
+            kind = "fixed_date"
+            pattern = "today"
+            value = 0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(definitions.prefilter(), None);
+    }
+
+    #[test]
+    fn test_disabled_definitions() {
+        let definitions = ExtractorDefinitions::parse(
+            r#"
+            [[extractor]]
+            name = "noon"
+            kind = "fixed_time"
+            pattern = "noon"
+            value = "12:00"
+            priority = 1
+            enabled = false
+
+            [[extractor]]
+            name = "today"
+            kind = "fixed_date"
+            pattern = "today"
+            value = 0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(definitions.time_extractors(true).len(), 0);
+        assert_eq!(definitions.date_extractors().len(), 1);
+    }
+
+    #[test]
+    fn test_zone_suffix() {
+        let definitions = ExtractorDefinitions::parse(
+            r#"
+            [[extractor]]
+            name = "24_hour"
+            kind = "dynamic_time"
+            pattern = '(?i:(?P<hours>\d\d):(?P<minutes>\d\d){zone_suffix})'
+            priority = 1
+            "#,
+        )
+        .unwrap();
+        assert_eq!(definitions.time_extractors(false).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_prefilter() {
+        let result = ExtractorDefinitions::parse(r"prefilter = ['\d', '(noon']");
+        assert!(matches!(result, Err(DefinitionError::InvalidPrefilter(_))));
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let result = ExtractorDefinitions::parse(
+            r#"
+            [[extractor]]
+            name = "noon"
+            kind = "fixed_time"
+            patern = "noon"
+            "#,
+        );
+        assert!(matches!(result, Err(DefinitionError::Parse(_))));
+    }
+
+    macro_rules! test_parse_error_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = ExtractorDefinitions::parse($input_text)
+                    .map(|_| ())
+                    .map_err(|error| error.to_string());
+                assert_eq!(actual, Err(String::from($expected)));
+            }
+            )*
+        };
+    }
+
+    test_parse_error_data! {
+        test_parse_unknown_kind {
+            text: r#"
+            [[extractor]]
+            name = "noon"
+            kind = "fixed_clock"
+            pattern = "noon"
+            "#,
+            expected: "Extractor [noon] has unknown kind [fixed_clock].",
+        },
+        test_parse_duplicate_name {
+            text: r#"
+            [[extractor]]
+            name = "today"
+            kind = "fixed_date"
+            pattern = "today"
+            value = 0
+
+            [[extractor]]
+            name = "today"
+            kind = "fixed_date"
+            pattern = "tonight"
+            value = 0
+            "#,
+            expected: "Extractor [today] is defined more than once.",
+        },
+        test_parse_missing_pattern {
+            text: r#"
+            [[extractor]]
+            name = "am_pm"
+            kind = "dynamic_time"
+            priority = 1
+            "#,
+            expected: "Extractor [am_pm] of kind [dynamic_time] is missing the pattern field.",
+        },
+        test_parse_missing_priority {
+            text: r#"
+            [[extractor]]
+            name = "am_pm"
+            kind = "dynamic_time"
+            pattern = '(?P<hours>\d+):(?P<minutes>\d+)'
+            "#,
+            expected: "Extractor [am_pm] of kind [dynamic_time] is missing the priority field.",
+        },
+        test_parse_unexpected_field {
+            text: r#"
+            [[extractor]]
+            name = "weekday"
+            kind = "dynamic_date"
+            pattern = '(?P<weekday>monday)'
+            priority = 1
+            "#,
+            expected: "Extractor [weekday] of kind [dynamic_date] doesn't take the priority field.",
+        },
+        test_parse_invalid_pattern {
+            text: r#"
+            [[extractor]]
+            name = "noon"
+            kind = "fixed_time"
+            pattern = "(noon"
+            value = "12:00"
+            priority = 1
+            "#,
+            expected: "Extractor [noon] is invalid: Failed to compile regex. regex parse error:\n    (noon\n    ^\nerror: unclosed group",
+        },
+        test_parse_missing_capture_groups {
+            text: r#"
+            [[extractor]]
+            name = "24_hour"
+            kind = "dynamic_time"
+            pattern = '(?P<hours>\d\d)h'
+            priority = 1
+            "#,
+            expected: "Extractor [24_hour] is invalid: Regex is missing the named capture groups [\"minutes\"].",
+        },
+        test_parse_unexpected_day_part_hints_capture_groups {
+            text: r#"
+            [[extractor]]
+            name = "inferred"
+            kind = "inferred_time"
+            pattern = 'at (?P<hours>\d+)'
+            priority = 0
+            day_part_hints = '(?P<day_part>tonight)(?P<hours>\d+)'
+            "#,
+            expected: "Extractor [inferred] is invalid: Regex contains the unexpected named capture groups [\"hours\"], expected only groups from [\"day_part\"].",
+        },
+        test_parse_invalid_fixed_time {
+            text: r#"
+            [[extractor]]
+            name = "noon"
+            kind = "fixed_time"
+            pattern = "noon"
+            value = "12pm"
+            priority = 1
+            "#,
+            expected: "Extractor [noon] has an invalid value: expected a time such as \"13:30\" but was \"12pm\"",
+        },
+        test_parse_invalid_fixed_date {
+            text: r#"
+            [[extractor]]
+            name = "today"
+            kind = "fixed_date"
+            pattern = "today"
+            value = "0"
+            "#,
+            expected: "Extractor [today] has an invalid value: expected a number of days from today but was \"0\"",
+        },
+        test_parse_invalid_zone_policy {
+            text: r#"
+            [[extractor]]
+            name = "am_pm"
+            kind = "dynamic_time"
+            pattern = '(?P<hours>\d+):(?P<minutes>\d+)'
+            priority = 1
+            ambiguous_zone_policy = "guess"
+            "#,
+            expected: "Extractor [am_pm] has an invalid ambiguous_zone_policy: Unknown ambiguous zone policy [guess], expected one of prefer_first, author_zone or skip",
+        },
+    }
+}
@@ -0,0 +1,218 @@
+use serde_json::Value;
+use serenity::{builder::CreateEmbed, utils};
+
+// Discord's limits on embeds, past which a message is rejected.
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+const MAX_FOOTER_LENGTH: usize = 2048;
+const MAX_FIELDS: usize = 25;
+const MAX_EMBEDS: usize = 10;
+// The most characters across the titles, descriptions, fields and footers of a message's
+// embeds.
+const MAX_TOTAL_LENGTH: usize = 6000;
+
+/// An embed showing a converted time, which is kept within Discord's limits by shortening
+/// any text that's too long and leaving out any fields past the limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeEmbed {
+    title: String,
+    description: Option<String>,
+    fields: Vec<(String, String)>,
+    footer: Option<String>,
+    colour: u32,
+}
+
+#[allow(dead_code)]
+impl TimeEmbed {
+    pub fn new(title: &str, colour: u32) -> Self {
+        Self {
+            title: truncate(title, MAX_TITLE_LENGTH),
+            description: None,
+            fields: Vec::new(),
+            footer: None,
+            colour,
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(truncate(description, MAX_DESCRIPTION_LENGTH));
+        self
+    }
+
+    /// Adds a field, which is shown next to the other fields where there's room.
+    pub fn field(mut self, name: &str, value: &str) -> Self {
+        if self.fields.len() < MAX_FIELDS {
+            self.fields.push((
+                truncate(name, MAX_FIELD_NAME_LENGTH),
+                truncate(value, MAX_FIELD_VALUE_LENGTH),
+            ));
+        }
+        self
+    }
+
+    pub fn footer(mut self, footer: &str) -> Self {
+        self.footer = Some(truncate(footer, MAX_FOOTER_LENGTH));
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// The number of characters that count towards Discord's limit across a message's embeds.
+    pub fn length(&self) -> usize {
+        let texts = std::iter::once(&self.title)
+            .chain(&self.description)
+            .chain(
+                self.fields
+                    .iter()
+                    .flat_map(|(name, value)| vec![name, value]),
+            )
+            .chain(&self.footer);
+        texts.map(|text| text.chars().count()).sum()
+    }
+
+    pub fn to_create_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        embed.title(&self.title).colour(self.colour);
+
+        if let Some(description) = &self.description {
+            embed.description(description);
+        }
+        for (name, value) in &self.fields {
+            embed.field(name, value, true);
+        }
+        if let Some(footer) = &self.footer {
+            embed.footer(|create_footer| create_footer.text(footer));
+        }
+
+        embed
+    }
+
+    pub fn to_json(&self) -> Value {
+        Value::Object(utils::hashmap_to_json_map(self.to_create_embed().0))
+    }
+}
+
+/// Whether the embeds can all be sent in one message. Embeds past the limits would otherwise
+/// have to be left out, so the conversions should be laid out some other way.
+pub fn fit_in_message(embeds: &[TimeEmbed]) -> bool {
+    embeds.len() <= MAX_EMBEDS
+        && embeds.iter().map(TimeEmbed::length).sum::<usize>() <= MAX_TOTAL_LENGTH
+}
+
+/// Shortens the text to at most the given number of characters, ending it with an ellipsis
+/// when it's shortened.
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return String::from(text);
+    }
+
+    let mut truncated: String = text.chars().take(max_length - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! test_truncate_data {
+        ($($name:ident{text: $input_text:expr, max_length: $input_max_length:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                assert_eq!(truncate($input_text, $input_max_length), $expected);
+            }
+            )*
+        };
+    }
+
+    test_truncate_data! {
+        test_truncate_short {
+            text: "5pm",
+            max_length: 5,
+            expected: "5pm",
+        },
+        test_truncate_exact {
+            text: "12:30",
+            max_length: 5,
+            expected: "12:30",
+        },
+        test_truncate_long {
+            text: "half past five",
+            max_length: 5,
+            expected: "half…",
+        },
+        test_truncate_multibyte {
+            text: "5–7pm in Zürich",
+            max_length: 4,
+            expected: "5–7…",
+        },
+    }
+
+    #[test]
+    fn test_fields_past_limit() {
+        let embed = (0..30).fold(TimeEmbed::new("5pm", 0), |embed, index| {
+            embed.field(&index.to_string(), "5:00 PM")
+        });
+
+        assert_eq!(embed.fields().len(), MAX_FIELDS);
+        assert_eq!(embed.fields().last().unwrap().0, "24");
+    }
+
+    #[test]
+    fn test_length() {
+        let embed = TimeEmbed::new("5pm", 0)
+            .description("in 2 hours")
+            .field("UK", "5:00 PM")
+            .footer("Zürich");
+
+        assert_eq!(embed.length(), 3 + 10 + 2 + 7 + 6);
+    }
+
+    #[test]
+    fn test_fit_in_message() {
+        let embeds = vec![TimeEmbed::new("5pm", 0).field("UK", "5:00 PM"); MAX_EMBEDS];
+        assert!(fit_in_message(&embeds));
+    }
+
+    #[test]
+    fn test_fit_in_message_too_many() {
+        let embeds = vec![TimeEmbed::new("5pm", 0).field("UK", "5:00 PM"); MAX_EMBEDS + 1];
+        assert!(!fit_in_message(&embeds));
+    }
+
+    #[test]
+    fn test_fit_in_message_too_long() {
+        let description = "5".repeat(MAX_DESCRIPTION_LENGTH);
+        let embeds = vec![TimeEmbed::new("5pm", 0).description(&description); 2];
+        assert!(!fit_in_message(&embeds));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let embed = TimeEmbed::new("'5pm'", 0x5865F2)
+            .description("<t:1614891600:t>")
+            .field("UK", "5:00 PM GMT")
+            .footer("Converted from Europe/London");
+
+        assert_eq!(
+            embed.to_json(),
+            serde_json::json!({
+                "title": "'5pm'",
+                "color": 0x5865F2,
+                "description": "<t:1614891600:t>",
+                "fields": [{"name": "UK", "value": "5:00 PM GMT", "inline": true}],
+                "footer": {"text": "Converted from Europe/London"},
+                "type": "rich",
+            })
+        );
+    }
+}
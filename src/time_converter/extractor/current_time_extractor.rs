@@ -1,9 +1,12 @@
 use chrono::TimeZone;
 use regex::Regex;
 
-use crate::{extractor::Extractor, time_converter::model::TimeComponents};
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::TimeComponents,
+};
 
-use super::TimeExtractorContext;
+use super::{extractor_error::ExtractorResult, TimeExtractorContext};
 
 #[derive(Debug)]
 pub struct CurrentTimeExtractor {
@@ -12,21 +15,29 @@ pub struct CurrentTimeExtractor {
 
 #[allow(dead_code)]
 impl CurrentTimeExtractor {
-    pub fn new(regex: &str) -> Self {
-        let compiled_regex = Regex::new(regex).expect("Failed to compile regex.");
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
 
-        Self {
+        Ok(Self {
             regex: compiled_regex,
-        }
+        })
     }
 }
 
 impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, TimeComponents> for CurrentTimeExtractor {
-    fn extract(&self, text: &str, ctx: &TimeExtractorContext<Tz>) -> Vec<TimeComponents> {
+    fn extract_matches(
+        &self,
+        text: &str,
+        ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<TimeComponents>> {
         let mut result = Vec::new();
 
-        if self.regex.is_match(text) {
-            result.push(ctx.message_time());
+        if let Some(found) = self.regex.find(text) {
+            result.push(Extraction::new(
+                found.range(),
+                found.as_str(),
+                ctx.message_time(),
+            ));
         }
 
         result
@@ -54,43 +65,43 @@ mod test {
 
     test_extract_data! {
         test_extract_no_match {
-            extractor: CurrentTimeExtractor::new(r"now"),
+            extractor: CurrentTimeExtractor::new(r"now").unwrap(),
             text: "Never!",
             msg_time: TimeComponents::of(10, 15, TimeKind::PM),
             expected: vec![],
         },
         test_extract_single_match_1 {
-            extractor: CurrentTimeExtractor::new(r"now"),
+            extractor: CurrentTimeExtractor::new(r"now").unwrap(),
             text: "The time is now.",
             msg_time: TimeComponents::of(1, 5, TimeKind::AM),
             expected: vec![TimeComponents::of(1, 5, TimeKind::AM)],
         },
         test_extract_single_match_2{
-            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)"),
+            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)").unwrap(),
             text: "whattimeisitrightnow",
             msg_time: TimeComponents::of(3, 55, TimeKind::PM),
             expected: vec![TimeComponents::of(3, 55, TimeKind::PM)],
         },
         test_extract_single_match_3{
-            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)"),
+            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)").unwrap(),
             text: "what time is it right now?",
             msg_time: TimeComponents::of(14, 25, TimeKind::Military),
             expected: vec![TimeComponents::of(14, 25, TimeKind::Military)],
         },
         test_extract_single_match_4{
-            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)"),
+            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)").unwrap(),
             text: "WhAt  TiMeIs     It RiGhTnOw???",
             msg_time: TimeComponents::of(1, 5, TimeKind::AM),
             expected: vec![TimeComponents::of(1, 5, TimeKind::AM)],
         },
         test_extract_no_match_2{
-            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)"),
+            extractor: CurrentTimeExtractor::new(r"^(?i:what\W*time\W*is\W*it\W*right\W*now[?]*)").unwrap(),
             text: "Shouldn't match what time is it right now?",
             msg_time: TimeComponents::of(14, 25, TimeKind::Military),
             expected: vec![],
         },
         test_extract_multi_match_1{
-            extractor: CurrentTimeExtractor::new(r"[nN]ow"),
+            extractor: CurrentTimeExtractor::new(r"[nN]ow").unwrap(),
             text: "Can we do it now? How about now? Now?",
             msg_time: TimeComponents::of(15, 11, TimeKind::Military),
             expected: vec![TimeComponents::of(15, 11, TimeKind::Military)],
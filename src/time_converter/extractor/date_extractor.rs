@@ -0,0 +1,35 @@
+use chrono::{NaiveDate, TimeZone};
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::DateComponents,
+};
+
+use super::{DynamicDateExtractor, FixedDateExtractor, TimeExtractorContext};
+
+// Implemented for the extractors of dates rather than for any extractor, so that it doesn't
+// overlap with the combinators that can extract any type.
+macro_rules! impl_date_conversion {
+    ($($extractor:ty),*) => {
+        $(
+        impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, NaiveDate> for $extractor {
+            fn extract_matches(
+                &self,
+                text: &str,
+                ctx: &TimeExtractorContext<Tz>,
+            ) -> Vec<Extraction<NaiveDate>> {
+                let today_in_local_tz = ctx.today();
+                let dates: Vec<Extraction<DateComponents>> = self.extract_matches(text, ctx);
+                dates
+                    .into_iter()
+                    .filter_map(|extraction| {
+                        extraction.and_then(|date| date.resolve(today_in_local_tz))
+                    })
+                    .collect()
+            }
+        }
+        )*
+    };
+}
+
+impl_date_conversion!(DynamicDateExtractor, FixedDateExtractor);
@@ -0,0 +1,124 @@
+use chrono::{DateTime, TimeZone, Utc};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::{DiscordTimestamp, TimestampStyle},
+};
+
+use super::TimeExtractorContext;
+
+static DISCORD_TIMESTAMP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<t:(?P<timestamp>-?\d{1,13})(?::(?P<style>[tTdDfFR]))?>")
+        .expect("Failed to compile regex.")
+});
+
+/// Extracts Discord timestamp markup, such as `<t:1700000000:f>`, which pins down an exact
+/// instant regardless of the author's time zone.
+#[derive(Debug, Default)]
+pub struct DiscordTimestampExtractor;
+
+#[allow(dead_code)]
+impl DiscordTimestampExtractor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Extractor<(), DiscordTimestamp> for DiscordTimestampExtractor {
+    fn extract_matches(&self, text: &str, _: &()) -> Vec<Extraction<DiscordTimestamp>> {
+        DISCORD_TIMESTAMP_REGEX
+            .captures_iter(text)
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let timestamp = process_captures(&captures)?;
+                Some(Extraction::new(found.range(), found.as_str(), timestamp))
+            })
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, DateTime<Utc>>
+    for DiscordTimestampExtractor
+{
+    fn extract_matches(
+        &self,
+        text: &str,
+        _: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<DateTime<Utc>>> {
+        let timestamps: Vec<Extraction<DiscordTimestamp>> = self.extract_matches(text, &());
+        timestamps
+            .into_iter()
+            .map(|extraction| extraction.map(|timestamp| timestamp.instant()))
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+fn process_captures(captures: &Captures) -> Option<DiscordTimestamp> {
+    let seconds = captures.name("timestamp")?.as_str().parse::<i64>().ok()?;
+    let instant = Utc.timestamp_opt(seconds, 0).single()?;
+    let style = match captures.name("style") {
+        Some(style) => TimestampStyle::from_flag(style.as_str().chars().next()?)?,
+        None => TimestampStyle::default(),
+    };
+
+    Some(DiscordTimestamp::new(instant, style))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::time_converter::model::{TimeComponents, TimeKind};
+
+    use super::*;
+
+    macro_rules! test_extract_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual: Vec<DiscordTimestamp> = DiscordTimestampExtractor::new().extract($input_text, &());
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_no_match {
+            text: "<t:abc:f> and t:1700000000:f",
+            expected: vec![],
+        },
+        test_extract_default_style {
+            text: "Starts <t:1700000000>",
+            expected: vec![DiscordTimestamp::new(Utc.timestamp(1_700_000_000, 0), TimestampStyle::ShortDateTime)],
+        },
+        test_extract_every_style {
+            text: "<t:1:t><t:2:T><t:3:d><t:4:D><t:5:f><t:6:F><t:7:R>",
+            expected: vec![
+                DiscordTimestamp::new(Utc.timestamp(1, 0), TimestampStyle::ShortTime),
+                DiscordTimestamp::new(Utc.timestamp(2, 0), TimestampStyle::LongTime),
+                DiscordTimestamp::new(Utc.timestamp(3, 0), TimestampStyle::ShortDate),
+                DiscordTimestamp::new(Utc.timestamp(4, 0), TimestampStyle::LongDate),
+                DiscordTimestamp::new(Utc.timestamp(5, 0), TimestampStyle::ShortDateTime),
+                DiscordTimestamp::new(Utc.timestamp(6, 0), TimestampStyle::LongDateTime),
+                DiscordTimestamp::new(Utc.timestamp(7, 0), TimestampStyle::Relative),
+            ],
+        },
+        test_extract_unknown_style {
+            text: "<t:1700000000:x>",
+            expected: vec![],
+        },
+    }
+
+    #[test]
+    fn test_extract_instants_deduplicates_styles() {
+        let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+        let actual: Vec<DateTime<Utc>> =
+            DiscordTimestampExtractor::new().extract("<t:1700000000:t> (<t:1700000000:R>)", &ctx);
+        assert_eq!(actual, vec![Utc.timestamp(1_700_000_000, 0)]);
+    }
+}
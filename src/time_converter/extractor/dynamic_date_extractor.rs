@@ -0,0 +1,222 @@
+use std::{collections::HashSet, str::FromStr};
+
+use chrono::{TimeZone, Weekday};
+use fancy_regex::{Captures, Regex};
+use itertools::Itertools;
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::DateComponents,
+};
+
+use super::{
+    extractor_error::{check_allowed_groups, invalid_groups, ExtractorResult},
+    TimeExtractorContext,
+};
+
+#[derive(Debug)]
+pub struct DynamicDateExtractor {
+    regex: Regex,
+}
+
+const WEEKDAY_CAPTURE_NAME: &str = "weekday";
+const MONTH_CAPTURE_NAME: &str = "month";
+const DAY_CAPTURE_NAME: &str = "day";
+const YEAR_CAPTURE_NAME: &str = "year";
+
+const ALLOWED_CAPTURE_GROUPS: [&str; 4] = [
+    WEEKDAY_CAPTURE_NAME,
+    MONTH_CAPTURE_NAME,
+    DAY_CAPTURE_NAME,
+    YEAR_CAPTURE_NAME,
+];
+
+const MONTH_ABBREVIATIONS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+#[allow(dead_code)]
+impl DynamicDateExtractor {
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Self::validate_regex(&compiled_regex)?;
+
+        Ok(Self {
+            regex: compiled_regex,
+        })
+    }
+
+    fn validate_regex(regex: &Regex) -> ExtractorResult<()> {
+        let names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        let is_weekday = names.contains(WEEKDAY_CAPTURE_NAME);
+        let is_calendar_date =
+            names.contains(MONTH_CAPTURE_NAME) && names.contains(DAY_CAPTURE_NAME);
+
+        if !is_weekday && !is_calendar_date {
+            return Err(invalid_groups(
+                &names,
+                "either the \"weekday\" named capture group or both the \"month\" and \"day\" named capture groups",
+            ));
+        }
+
+        check_allowed_groups(&names, &ALLOWED_CAPTURE_GROUPS)
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, DateComponents> for DynamicDateExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        _ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<DateComponents>> {
+        self.regex
+            .captures_iter(text)
+            .filter_map(|result| result.ok())
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let date = process_captures(&captures)?;
+                Some(Extraction::new(found.range(), found.as_str(), date))
+            })
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+fn extract_capture<T: FromStr>(captures: &Captures, name: &str) -> Option<T> {
+    captures
+        .name(name)
+        .map(|capture| capture.as_str())
+        .map(|string| string.parse::<T>())
+        .and_then(|result| result.ok())
+}
+
+fn parse_month(text: &str) -> Option<u32> {
+    if let Ok(month) = text.parse::<u32>() {
+        return Some(month).filter(|month| (1..=12).contains(month));
+    }
+
+    let prefix = text.get(..3)?.to_lowercase();
+    MONTH_ABBREVIATIONS
+        .iter()
+        .position(|abbreviation| *abbreviation == prefix)
+        .map(|index| index as u32 + 1)
+}
+
+fn process_captures(captures: &Captures) -> Option<DateComponents> {
+    let month = extract_capture::<String>(captures, MONTH_CAPTURE_NAME);
+    let day = extract_capture::<u32>(captures, DAY_CAPTURE_NAME);
+
+    if let (Some(month), Some(day)) = (month, day) {
+        let year = extract_capture::<i32>(captures, YEAR_CAPTURE_NAME).map(|year| match year {
+            0..=99 => 2000 + year,
+            _ => year,
+        });
+
+        return Some(DateComponents::Calendar {
+            month: parse_month(&month)?,
+            day,
+            year,
+        });
+    }
+
+    extract_capture::<Weekday>(captures, WEEKDAY_CAPTURE_NAME).map(DateComponents::Weekday)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::time_converter::{
+        extractor::ExtractorError,
+        model::{TimeComponents, TimeKind},
+    };
+    use chrono::Utc;
+
+    use super::*;
+
+    const WEEKDAY_REGEX: &str =
+        r"(?i:\b(?P<weekday>monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b)";
+    const MONTH_DAY_REGEX: &str = r"(?i:\b(?P<month>jan(?:uary)?|feb(?:ruary)?|mar(?:ch)?|apr(?:il)?|may|june?|july?|aug(?:ust)?|sept?(?:ember)?|oct(?:ober)?|nov(?:ember)?|dec(?:ember)?)\.?\s+(?P<day>[12]\d|3[01]|0?[1-9])(?:st|nd|rd|th)?(?:,?\s+(?P<year>\d{4}))?\b)";
+    const DAY_MONTH_REGEX: &str = r"(?i:\b(?P<day>[12]\d|3[01]|0?[1-9])(?:st|nd|rd|th)?\s+(?:of\s+)?(?:(?<=\d\s)(?!may\b)|(?<!\d\s))(?P<month>jan(?:uary)?|feb(?:ruary)?|mar(?:ch)?|apr(?:il)?|may|june?|july?|aug(?:ust)?|sept?(?:ember)?|oct(?:ober)?|nov(?:ember)?|dec(?:ember)?)\.?(?:,?\s+(?P<year>\d{4}))?\b)";
+    const ISO_REGEX: &str =
+        r"\b(?P<year>\d{4})-(?P<month>0[1-9]|1[0-2])-(?P<day>0[1-9]|[12]\d|3[01])\b";
+
+    #[test]
+    fn test_missing_capture_groups_1() {
+        let error = DynamicDateExtractor::new(r"(?P<month>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::InvalidCaptureGroups { found, .. } if found == vec!["month"]
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_capture_groups_1() {
+        let error = DynamicDateExtractor::new(r"(?P<weekday>\w+) (?P<hours>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::UnexpectedCaptureGroups { unexpected, .. } if unexpected == vec!["hours"]
+        ));
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{regex: $input_regex:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = DynamicDateExtractor::new($input_regex).unwrap();
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+                let actual: Vec<DateComponents> = extractor.extract($input_text, &ctx);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_weekday {
+            regex: WEEKDAY_REGEX,
+            text: "Friday 8pm?",
+            expected: vec![DateComponents::Weekday(Weekday::Fri)],
+        },
+        test_extract_weekday_not_a_word_prefix {
+            regex: WEEKDAY_REGEX,
+            text: "Fridays are great",
+            expected: vec![],
+        },
+        test_extract_month_day {
+            regex: MONTH_DAY_REGEX,
+            text: "Dec 3 9am",
+            expected: vec![DateComponents::Calendar { month: 12, day: 3, year: None }],
+        },
+        test_extract_month_day_with_suffix_and_year {
+            regex: MONTH_DAY_REGEX,
+            text: "on September 21st, 2022 at noon",
+            expected: vec![DateComponents::Calendar { month: 9, day: 21, year: Some(2022) }],
+        },
+        test_extract_month_day_ignores_other_words {
+            regex: MONTH_DAY_REGEX,
+            text: "You may 3D print it in march",
+            expected: vec![],
+        },
+        test_extract_day_month {
+            regex: DAY_MONTH_REGEX,
+            text: "3rd of December or 4 Jan 2022",
+            expected: vec![
+                DateComponents::Calendar { month: 12, day: 3, year: None },
+                DateComponents::Calendar { month: 1, day: 4, year: Some(2022) },
+            ],
+        },
+        test_extract_day_month_may {
+            regex: DAY_MONTH_REGEX,
+            text: "At 3 may be too late, how about the 5th May?",
+            expected: vec![DateComponents::Calendar { month: 5, day: 5, year: None }],
+        },
+        test_extract_iso_date {
+            regex: ISO_REGEX,
+            text: "2021-12-03 17:00",
+            expected: vec![DateComponents::Calendar { month: 12, day: 3, year: Some(2021) }],
+        },
+    }
+}
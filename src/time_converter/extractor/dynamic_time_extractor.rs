@@ -1,105 +1,120 @@
-use core::panic;
 use std::{collections::HashSet, str::FromStr};
 
 use chrono::TimeZone;
 use fancy_regex::{Captures, Regex};
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 use crate::{
-    extractor::Extractor,
-    time_converter::model::{TimeComponents, TimeKind},
+    extractor::{Extraction, Extractor},
+    time_converter::model::{
+        DayPart, TimeComponents, TimeExpression, TimeKind, ENGLISH_DAY_PART_WORDS,
+    },
 };
 
-use super::TimeExtractorContext;
+use super::{
+    extractor_error::{check_allowed_groups, check_required_groups, ExtractorResult},
+    time_zone_aliases::{lookup_zone, AmbiguousZonePolicy},
+    TimeExtractorContext,
+};
 #[derive(Debug)]
 pub struct DynamicTimeExtractor {
     regex: Regex,
+    ambiguous_zone_policy: AmbiguousZonePolicy,
+    day_part_words: Vec<(String, DayPart)>,
 }
 
 const HOURS_CAPTURE_NAME: &str = "hours";
 const MINUTES_CAPTURE_NAME: &str = "minutes";
 const TIME_KIND_CAPTURE_NAME: &str = "time_kind";
+const DAY_PART_CAPTURE_NAME: &str = "day_part";
+const ZONE_CAPTURE_NAME: &str = "zone";
 
-// TODO : Consider moving to a separate set utils file
-macro_rules! set {
-    ( $( $item:expr ),* ) => {
-        {
-            let mut set = HashSet::new();
-            $(
-                set.insert($item);
-            )*
-            set
-        }
-    };
-}
+const ALLOWED_CAPTURE_GROUPS: [&str; 5] = [
+    HOURS_CAPTURE_NAME,
+    MINUTES_CAPTURE_NAME,
+    TIME_KIND_CAPTURE_NAME,
+    DAY_PART_CAPTURE_NAME,
+    ZONE_CAPTURE_NAME,
+];
 
-static ALLOWED_CAPTURE_GROUPS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    set![
-        HOURS_CAPTURE_NAME,
-        MINUTES_CAPTURE_NAME,
-        TIME_KIND_CAPTURE_NAME
-    ]
-});
-
-static REQUIRED_CAPTURE_GROUPS: Lazy<HashSet<&str>> = Lazy::new(|| {
-    set![
-        HOURS_CAPTURE_NAME,
-        MINUTES_CAPTURE_NAME,
-        TIME_KIND_CAPTURE_NAME
-    ]
-});
+const REQUIRED_CAPTURE_GROUPS: [&str; 2] = [HOURS_CAPTURE_NAME, MINUTES_CAPTURE_NAME];
 
 #[allow(dead_code)]
 impl DynamicTimeExtractor {
-    pub fn new(regex: &str) -> Self {
-        let compiled_regex = Regex::new(regex).expect("Failed to compile regex.");
-        Self::validate_regex(&compiled_regex);
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Self::validate_regex(&compiled_regex)?;
 
-        Self {
+        Ok(Self {
             regex: compiled_regex,
-        }
+            ambiguous_zone_policy: AmbiguousZonePolicy::PreferFirst,
+            day_part_words: ENGLISH_DAY_PART_WORDS
+                .iter()
+                .map(|(word, day_part)| (word.to_string(), *day_part))
+                .collect(),
+        })
     }
 
-    fn validate_regex(regex: &Regex) {
-        let names: HashSet<&str> = regex.capture_names().flatten().collect();
-
-        if !Self::contains_all(&names, &REQUIRED_CAPTURE_GROUPS) {
-            panic!(
-                "Regex must contain all of the following named capture groups {:?} but contains {:?}",
-                *REQUIRED_CAPTURE_GROUPS, names
-            );
-        }
+    pub fn ambiguous_zone_policy(mut self, policy: AmbiguousZonePolicy) -> Self {
+        self.ambiguous_zone_policy = policy;
+        self
+    }
 
-        if !Self::contains_all(&ALLOWED_CAPTURE_GROUPS, &names) {
-            panic!(
-                "Regex must only contain named captue groups from {:?} but contains {:?}",
-                *ALLOWED_CAPTURE_GROUPS, names
-            );
-        }
+    /// Sets the words that name each part of the day in the `day_part` capture group, which
+    /// are English by default.
+    pub fn day_part_words(mut self, words: &[(&str, DayPart)]) -> Self {
+        self.day_part_words = words
+            .iter()
+            .map(|(word, day_part)| (word.to_string(), *day_part))
+            .collect();
+        self
     }
 
-    // TODO : Consider moving to a separate set utils file
-    fn contains_all<T>(set_a: &HashSet<T>, set_b: &HashSet<T>) -> bool
-    where
-        T: Eq,
-        T: core::hash::Hash,
-    {
-        set_a.intersection(set_b).count() == set_b.len()
+    fn validate_regex(regex: &Regex) -> ExtractorResult<()> {
+        let names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        check_required_groups(&names, &REQUIRED_CAPTURE_GROUPS)?;
+        check_allowed_groups(&names, &ALLOWED_CAPTURE_GROUPS)
     }
 }
 
-impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, TimeComponents> for DynamicTimeExtractor {
-    fn extract(&self, text: &str, _ctx: &TimeExtractorContext<Tz>) -> Vec<TimeComponents> {
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, TimeExpression> for DynamicTimeExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        _ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<TimeExpression>> {
         self.regex
             .captures_iter(text)
             .filter_map(|result| result.ok())
-            .filter_map(|captures| process_captures(&captures))
-            .unique()
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let time = process_captures(&captures, &self.day_part_words)?;
+                let zone = self.process_zone_capture(&captures)?;
+                Some(Extraction::new(
+                    found.range(),
+                    found.as_str(),
+                    TimeExpression::new(time, zone),
+                ))
+            })
+            .unique_by(|extraction| *extraction.value())
             .collect()
     }
 }
 
+impl DynamicTimeExtractor {
+    /// Returns `None` if the time shouldn't be converted at all, otherwise the zone the
+    /// time was explicitly written in, if any.
+    fn process_zone_capture(&self, captures: &Captures) -> Option<Option<chrono_tz::Tz>> {
+        let zone_name = match extract_capture::<String>(captures, ZONE_CAPTURE_NAME) {
+            Some(zone_name) => zone_name,
+            None => return Some(None),
+        };
+
+        lookup_zone(&zone_name).resolve(self.ambiguous_zone_policy)
+    }
+}
+
 fn extract_capture<T: FromStr>(captures: &Captures, name: &str) -> Option<T> {
     captures
         .name(name)
@@ -108,43 +123,416 @@ fn extract_capture<T: FromStr>(captures: &Captures, name: &str) -> Option<T> {
         .and_then(|result| result.ok())
 }
 
-fn process_captures(captures: &Captures) -> Option<TimeComponents> {
+fn process_captures(
+    captures: &Captures,
+    day_part_words: &[(String, DayPart)],
+) -> Option<TimeComponents> {
     let hour = extract_capture::<u32>(captures, HOURS_CAPTURE_NAME)?;
     let minute = extract_capture::<u32>(captures, MINUTES_CAPTURE_NAME).unwrap_or(0);
-    let time_kind = extract_capture::<String>(captures, TIME_KIND_CAPTURE_NAME)
-        .map(|it| it.to_uppercase())
-        .and_then(|it| match it.as_str() {
-            "AM" => Some(TimeKind::AM),
-            "PM" => Some(TimeKind::PM),
-            _ => None,
-        })?;
+
+    // Regexes without a time kind or day part capture group, or where neither group
+    // participated in the match, describe a time on the 24-hour clock.
+    let time_kind = match (
+        extract_capture::<String>(captures, TIME_KIND_CAPTURE_NAME),
+        extract_capture::<String>(captures, DAY_PART_CAPTURE_NAME),
+    ) {
+        (Some(time_kind), _) => match time_kind.to_uppercase().as_str() {
+            "AM" => TimeKind::AM,
+            "PM" => TimeKind::PM,
+            _ => return None,
+        },
+        (None, Some(day_part)) => {
+            let day_part = day_part.split_whitespace().join(" ").to_lowercase();
+            DayPart::find(&day_part, day_part_words)?.time_kind(hour)
+        }
+        (None, None) => TimeKind::Military,
+    };
 
     TimeComponents::new(hour, minute, time_kind).ok()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::time_converter::model::TimeKind;
-    use chrono::Utc;
+    use std::sync::Arc;
+
+    use crate::time_converter::{
+        clock::FixedClock,
+        extractor::{ExtractorError, TIME_ZONE_SUFFIX_PATTERN},
+        model::{ExtractedTime, LocalInstant, TimeKind},
+    };
+    use chrono::{DateTime, Utc};
+    use chrono_tz::{America, Asia, Europe};
 
     use super::*;
 
     #[test]
-    #[should_panic]
-    fn test_missing_capture_groups_1() {
-        DynamicTimeExtractor::new(r"");
+    fn test_missing_capture_groups() {
+        let error = DynamicTimeExtractor::new(r"(?P<hours>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::MissingCaptureGroups { missing } if missing == vec!["minutes"]
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_capture_groups() {
+        let error = DynamicTimeExtractor::new(r"(?P<hours>\d+):(?P<minutes>\d+)(?P<seconds>:\d+)?")
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::UnexpectedCaptureGroups { unexpected, .. } if unexpected == vec!["seconds"]
+        ));
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let error = DynamicTimeExtractor::new(r"(?P<hours>\d+").unwrap_err();
+
+        assert!(matches!(error, ExtractorError::InvalidFancyRegex(_)));
     }
 
     #[test]
     fn test() {
         let extractor = DynamicTimeExtractor::new(
             r"(?i:(?<!\w)(?P<hours>(?:1[012])|(?:0?[123456789]))\s*(?::\s*(?P<minutes>(?:[12345]\d)|(?:0\d)))?\s*(?P<time_kind>[ap]m)(?!\w))",
-        );
+        ).unwrap();
 
         let text = String::from("3am 4america 5am-6pm 5am 17am 17pm");
         let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
-        let actual: Vec<TimeComponents> = extractor.extract(&text, &ctx);
+        let actual: Vec<TimeExpression> = extractor.extract(&text, &ctx);
 
         println!("{:?}", actual)
     }
+
+    const AM_PM_REGEX: &str = r"(?i:(?<!\w)(?P<hours>(?:1[012])|(?:0?[123456789]))\s*(?::\s*(?P<minutes>(?:[12345]\d)|(?:0\d)))?\s*(?P<time_kind>[ap]m)(?!\w))";
+    const MILITARY_REGEX: &str = r"(?i:(?<![\w:.])(?P<hours>[01]\d|2[0-3])(?::|(?=[0-5]\d\s?h(?:rs|ours)?\b))(?P<minutes>[0-5]\d)(?:\s?h(?:rs|ours)?\b)?(?![\w:]|[.,]\d|\s*[ap]\.?m\b))";
+
+    #[test]
+    fn test_extract_matches_spans() {
+        let extractor = DynamicTimeExtractor::new(AM_PM_REGEX).unwrap();
+        let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+        let actual: Vec<Extraction<TimeComponents>> = extractor
+            .extract_matches("Either 5pm or 10:30 am", &ctx)
+            .into_iter()
+            .map(|extraction: Extraction<TimeExpression>| extraction.map(|time| time.time()))
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                Extraction::new(7..10, "5pm", TimeComponents::of(5, 0, TimeKind::PM)),
+                Extraction::new(14..22, "10:30 am", TimeComponents::of(10, 30, TimeKind::AM)),
+            ]
+        );
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{regex: $input_regex:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = DynamicTimeExtractor::new($input_regex).unwrap();
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+                let actual: Vec<TimeExpression> = extractor.extract($input_text, &ctx);
+                let actual_times: Vec<TimeComponents> = actual.iter().map(TimeExpression::time).collect();
+                assert_eq!(actual_times, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_am_pm_1 {
+            regex: AM_PM_REGEX,
+            text: "3am 4america 5:30pm",
+            expected: vec![TimeComponents::of(3, 0, TimeKind::AM), TimeComponents::of(5, 30, TimeKind::PM)],
+        },
+        test_extract_military_colon {
+            regex: MILITARY_REGEX,
+            text: "Let's start at 17:30.",
+            expected: vec![TimeComponents::of(17, 30, TimeKind::Military)],
+        },
+        test_extract_military_leading_zero {
+            regex: MILITARY_REGEX,
+            text: "09:00 works for me",
+            expected: vec![TimeComponents::of(9, 0, TimeKind::Military)],
+        },
+        test_extract_military_hours_suffix {
+            regex: MILITARY_REGEX,
+            text: "Briefing at 1730h, debrief at 2100 hours",
+            expected: vec![TimeComponents::of(17, 30, TimeKind::Military), TimeComponents::of(21, 0, TimeKind::Military)],
+        },
+        test_extract_military_colon_hours_suffix {
+            regex: MILITARY_REGEX,
+            text: "17:30h",
+            expected: vec![TimeComponents::of(17, 30, TimeKind::Military)],
+        },
+        test_extract_military_midnight {
+            regex: MILITARY_REGEX,
+            text: "00:00",
+            expected: vec![TimeComponents::of(0, 0, TimeKind::Military)],
+        },
+        test_extract_military_ignores_scores {
+            regex: MILITARY_REGEX,
+            text: "We won 3:2 and then 10:3",
+            expected: vec![],
+        },
+        test_extract_military_ignores_single_digit_hours {
+            regex: MILITARY_REGEX,
+            text: "9:00",
+            expected: vec![],
+        },
+        test_extract_military_ignores_version_numbers {
+            regex: MILITARY_REGEX,
+            text: "Upgrade from 1.10.3 to v2.12:30 or 12:30.5",
+            expected: vec![],
+        },
+        test_extract_military_ignores_durations {
+            regex: MILITARY_REGEX,
+            text: "The video is 01:23:45 long",
+            expected: vec![],
+        },
+        test_extract_military_ignores_bare_numbers {
+            regex: MILITARY_REGEX,
+            text: "It costs 1730 gold",
+            expected: vec![],
+        },
+        test_extract_military_ignores_am_pm {
+            regex: MILITARY_REGEX,
+            text: "10:30pm or 11:15 AM",
+            expected: vec![],
+        },
+        test_extract_military_ignores_invalid_times {
+            regex: MILITARY_REGEX,
+            text: "24:00 or 17:60",
+            expected: vec![],
+        },
+    }
+
+    const DAY_PART_REGEX: &str = r"(?i:(?<![\w:.])(?P<hours>1[0-2]|0?[1-9])(?::(?P<minutes>[0-5]\d))?\s+(?P<day_part>in\s+the\s+(?:morning|afternoon|evening)|at\s+(?:night|dawn))\b)";
+
+    macro_rules! test_extract_day_part_data {
+        ($($name:ident{words: $input_words:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = DynamicTimeExtractor::new(DAY_PART_REGEX).unwrap().day_part_words($input_words);
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+                let actual: Vec<TimeExpression> = extractor.extract($input_text, &ctx);
+                let actual_times: Vec<TimeComponents> = actual.iter().map(TimeExpression::time).collect();
+                assert_eq!(actual_times, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_day_part_data! {
+        test_extract_day_part_english {
+            words: &ENGLISH_DAY_PART_WORDS,
+            text: "9 in the morning or 7:30 in the evening",
+            expected: vec![TimeComponents::of(9, 0, TimeKind::AM), TimeComponents::of(7, 30, TimeKind::PM)],
+        },
+        test_extract_day_part_night {
+            words: &ENGLISH_DAY_PART_WORDS,
+            text: "11 at night until 2 at night",
+            expected: vec![TimeComponents::of(11, 0, TimeKind::PM), TimeComponents::of(2, 0, TimeKind::AM)],
+        },
+        test_extract_day_part_custom_words {
+            words: &[("dawn", DayPart::Morning)],
+            text: "5 at dawn",
+            expected: vec![TimeComponents::of(5, 0, TimeKind::AM)],
+        },
+        test_extract_day_part_unknown_word {
+            words: &[("dawn", DayPart::Morning)],
+            text: "5 in the evening",
+            expected: vec![],
+        },
+    }
+
+    fn zoned_regex(regex: &str) -> String {
+        // Insert the zone suffix before the closing parenthesis of the case insensitive group.
+        let (body, closing) = regex.split_at(regex.len() - 1);
+        format!("{}{}{}", body, *TIME_ZONE_SUFFIX_PATTERN, closing)
+    }
+
+    macro_rules! test_extract_zone_data {
+        ($($name:ident{regex: $input_regex:expr, policy: $policy:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = DynamicTimeExtractor::new(&zoned_regex($input_regex)).unwrap().ambiguous_zone_policy($policy);
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+                let actual: Vec<TimeExpression> = extractor.extract($input_text, &ctx);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_zone_data! {
+        test_extract_zone_abbreviation {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "Raid at 3pm EST",
+            expected: vec![TimeExpression::new(TimeComponents::of(3, 0, TimeKind::PM), Some(America::New_York))],
+        },
+        test_extract_zone_military {
+            regex: MILITARY_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "15:00 CET works",
+            expected: vec![TimeExpression::new(TimeComponents::of(15, 0, TimeKind::Military), Some(Europe::Berlin))],
+        },
+        test_extract_zone_country_alias {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "8pm UK time",
+            expected: vec![TimeExpression::new(TimeComponents::of(8, 0, TimeKind::PM), Some(Europe::London))],
+        },
+        test_extract_zone_city_alias {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "9am in new york",
+            expected: vec![TimeExpression::new(TimeComponents::of(9, 0, TimeKind::AM), Some(America::New_York))],
+        },
+        test_extract_zone_iana_name {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "10:30am Europe/Amsterdam",
+            expected: vec![TimeExpression::new(TimeComponents::of(10, 30, TimeKind::AM), Some(Europe::Amsterdam))],
+        },
+        test_extract_zone_missing {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "3pm at the usual place",
+            expected: vec![TimeExpression::new(TimeComponents::of(3, 0, TimeKind::PM), None)],
+        },
+        test_extract_zone_not_a_word_prefix {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "3pm establishing",
+            expected: vec![TimeExpression::new(TimeComponents::of(3, 0, TimeKind::PM), None)],
+        },
+        test_extract_zone_upper_case_alias {
+            regex: MILITARY_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "15:00 ET",
+            expected: vec![TimeExpression::new(TimeComponents::of(15, 0, TimeKind::Military), Some(America::New_York))],
+        },
+        test_extract_zone_ignores_et {
+            regex: MILITARY_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "meet at 15:00 et 16:00",
+            expected: vec![
+                TimeExpression::new(TimeComponents::of(15, 0, TimeKind::Military), None),
+                TimeExpression::new(TimeComponents::of(16, 0, TimeKind::Military), None),
+            ],
+        },
+        test_extract_zone_ignores_la {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "a las 5pm la cena",
+            expected: vec![TimeExpression::new(TimeComponents::of(5, 0, TimeKind::PM), None)],
+        },
+        test_extract_ambiguous_zone_prefer_first {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::PreferFirst,
+            text: "5pm IST",
+            expected: vec![TimeExpression::new(TimeComponents::of(5, 0, TimeKind::PM), Some(Asia::Kolkata))],
+        },
+        test_extract_ambiguous_zone_author_zone {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::AuthorZone,
+            text: "5pm CST",
+            expected: vec![TimeExpression::new(TimeComponents::of(5, 0, TimeKind::PM), None)],
+        },
+        test_extract_ambiguous_zone_skip {
+            regex: AM_PM_REGEX,
+            policy: AmbiguousZonePolicy::Skip,
+            text: "5pm CST or 6pm EST",
+            expected: vec![TimeExpression::new(TimeComponents::of(6, 0, TimeKind::PM), Some(America::New_York))],
+        },
+    }
+
+    macro_rules! test_extract_pinned_data {
+        ($($name:ident{now: $input_now:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = DynamicTimeExtractor::new(AM_PM_REGEX).unwrap();
+                let ctx = TimeExtractorContext::new(America::New_York, TimeComponents::of(9, 0, TimeKind::Military))
+                    .with_clock(Arc::new(FixedClock::new($input_now)));
+                let actual: Vec<DateTime<Utc>> = extractor.extract($input_text, &ctx);
+                let expected: Vec<DateTime<Utc>> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    // Daylight saving time starts in New York at 2am on March 14th, 2021.
+    test_extract_pinned_data! {
+        test_extract_pinned_before_dst {
+            now: Utc.ymd(2021, 3, 13).and_hms(17, 0, 0),
+            text: "5pm",
+            expected: vec![Utc.ymd(2021, 3, 13).and_hms(22, 0, 0)],
+        },
+        test_extract_pinned_after_dst {
+            now: Utc.ymd(2021, 3, 14).and_hms(17, 0, 0),
+            text: "5pm",
+            expected: vec![Utc.ymd(2021, 3, 14).and_hms(21, 0, 0)],
+        },
+        test_extract_pinned_local_date_behind_utc {
+            now: Utc.ymd(2021, 3, 14).and_hms(3, 0, 0),
+            text: "5pm",
+            expected: vec![Utc.ymd(2021, 3, 13).and_hms(22, 0, 0)],
+        },
+        test_extract_pinned_skipped_by_dst {
+            now: Utc.ymd(2021, 3, 14).and_hms(17, 0, 0),
+            text: "2:30am",
+            expected: vec![],
+        },
+    }
+
+    macro_rules! test_extract_pinned_extracted_time_data {
+        ($($name:ident{now: $input_now:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = DynamicTimeExtractor::new(AM_PM_REGEX).unwrap();
+                let ctx = TimeExtractorContext::new(America::New_York, TimeComponents::of(9, 0, TimeKind::Military))
+                    .with_clock(Arc::new(FixedClock::new($input_now)));
+                let actual: Vec<ExtractedTime> = extractor.extract($input_text, &ctx);
+                let expected: Vec<ExtractedTime> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    // Daylight saving time ends in New York at 2am on November 7th, 2021.
+    test_extract_pinned_extracted_time_data! {
+        test_extract_pinned_extracted_time_single {
+            now: Utc.ymd(2021, 3, 14).and_hms(17, 0, 0),
+            text: "5pm",
+            expected: vec![ExtractedTime::Instant(Utc.ymd(2021, 3, 14).and_hms(21, 0, 0).into())],
+        },
+        test_extract_pinned_extracted_time_skipped {
+            now: Utc.ymd(2021, 3, 14).and_hms(17, 0, 0),
+            text: "2:30am",
+            expected: vec![ExtractedTime::Instant(LocalInstant::Skipped {
+                earlier: Utc.ymd(2021, 3, 14).and_hms(6, 30, 0),
+                later: Utc.ymd(2021, 3, 14).and_hms(7, 30, 0),
+            })],
+        },
+        test_extract_pinned_extracted_time_repeated {
+            now: Utc.ymd(2021, 11, 7).and_hms(17, 0, 0),
+            text: "1:30am",
+            expected: vec![ExtractedTime::Instant(LocalInstant::Repeated {
+                earlier: Utc.ymd(2021, 11, 7).and_hms(5, 30, 0),
+                later: Utc.ymd(2021, 11, 7).and_hms(6, 30, 0),
+            })],
+        },
+    }
 }
@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use thiserror::Error;
+
+pub type ExtractorResult<T> = Result<T, ExtractorError>;
+
+/// Describes why an extractor couldn't be created from a regex.
+#[derive(Error, Debug)]
+pub enum ExtractorError {
+    #[error("Failed to compile regex. {0}")]
+    InvalidRegex(#[from] regex::Error),
+    #[error("Failed to compile regex. {0}")]
+    InvalidFancyRegex(#[from] fancy_regex::Error),
+    #[error("Regex is missing the named capture groups {missing:?}.")]
+    MissingCaptureGroups { missing: Vec<String> },
+    #[error(
+        "Regex contains the unexpected named capture groups {unexpected:?}, expected only groups from {allowed:?}."
+    )]
+    UnexpectedCaptureGroups {
+        unexpected: Vec<String>,
+        allowed: Vec<String>,
+    },
+    #[error("Regex must contain {expected} but contains {found:?}.")]
+    InvalidCaptureGroups {
+        expected: String,
+        found: Vec<String>,
+    },
+    #[error("Regex template is missing the placeholder {placeholder:?}.")]
+    MissingPlaceholder { placeholder: String },
+}
+
+/// Checks that a regex contains all of the required named capture groups.
+pub(super) fn check_required_groups(
+    names: &HashSet<&str>,
+    required: &[&str],
+) -> ExtractorResult<()> {
+    let missing: Vec<String> = required
+        .iter()
+        .filter(|name| !names.contains(*name))
+        .map(|name| name.to_string())
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ExtractorError::MissingCaptureGroups { missing })
+    }
+}
+
+/// Checks that a regex contains no named capture groups other than the allowed ones.
+pub(super) fn check_allowed_groups(names: &HashSet<&str>, allowed: &[&str]) -> ExtractorResult<()> {
+    let unexpected: Vec<String> = names
+        .iter()
+        .filter(|name| !allowed.contains(*name))
+        .sorted()
+        .map(|name| name.to_string())
+        .collect();
+
+    if unexpected.is_empty() {
+        Ok(())
+    } else {
+        Err(ExtractorError::UnexpectedCaptureGroups {
+            unexpected,
+            allowed: allowed.iter().map(|name| name.to_string()).collect(),
+        })
+    }
+}
+
+pub(super) fn invalid_groups(names: &HashSet<&str>, expected: &str) -> ExtractorError {
+    ExtractorError::InvalidCaptureGroups {
+        expected: String::from(expected),
+        found: names.iter().sorted().map(|name| name.to_string()).collect(),
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::TimeZone;
+use regex::Regex;
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::DateComponents,
+};
+
+use super::{extractor_error::ExtractorResult, TimeExtractorContext};
+
+#[derive(Debug)]
+pub struct FixedDateExtractor {
+    regex: Regex,
+    fixed_date: DateComponents,
+}
+
+#[allow(dead_code)]
+impl FixedDateExtractor {
+    pub fn new(regex: &str, value: DateComponents) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Ok(Self {
+            regex: compiled_regex,
+            fixed_date: value,
+        })
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, DateComponents> for FixedDateExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        _: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<DateComponents>> {
+        let mut result = Vec::new();
+
+        if let Some(found) = self.regex.find(text) {
+            result.push(Extraction::new(
+                found.range(),
+                found.as_str(),
+                self.fixed_date,
+            ));
+        };
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::time_converter::model::{TimeComponents, TimeKind};
+
+    use super::*;
+
+    macro_rules! test_extract_data {
+        ($($name:ident{extractor: $input_extractor:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let ctx = TimeExtractorContext::new(chrono::Utc, TimeComponents::of(5, 7, TimeKind::AM));
+                let actual: Vec<DateComponents> = $input_extractor.extract($input_text, &ctx);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_no_match_1 {
+            extractor: FixedDateExtractor::new(r"(?i:\btomorrow\b)", DateComponents::RelativeDays(1)).unwrap(),
+            text: "See you today.",
+            expected: vec![],
+        },
+        test_extract_single_match_1 {
+            extractor: FixedDateExtractor::new(r"(?i:\btomorrow\b)", DateComponents::RelativeDays(1)).unwrap(),
+            text: "Tomorrow at 5pm?",
+            expected: vec![DateComponents::RelativeDays(1)],
+        },
+        test_extract_multi_match_1 {
+            extractor: FixedDateExtractor::new(r"(?i:\btoday\b)", DateComponents::RelativeDays(0)).unwrap(),
+            text: "Today? Yes, today.",
+            expected: vec![DateComponents::RelativeDays(0)],
+        },
+    }
+}
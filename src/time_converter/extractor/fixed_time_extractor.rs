@@ -1,9 +1,12 @@
 use chrono::TimeZone;
 use regex::Regex;
 
-use crate::{extractor::Extractor, time_converter::model::TimeComponents};
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::TimeComponents,
+};
 
-use super::time_extractor_context::TimeExtractorContext;
+use super::{extractor_error::ExtractorResult, time_extractor_context::TimeExtractorContext};
 
 #[derive(Debug)]
 pub struct FixedTimeExtractor {
@@ -13,21 +16,29 @@ pub struct FixedTimeExtractor {
 
 #[allow(dead_code)]
 impl FixedTimeExtractor {
-    pub fn new(regex: &str, value: TimeComponents) -> Self {
-        let compiled_regex = Regex::new(regex).expect("Failed to compile regex.");
-        Self {
+    pub fn new(regex: &str, value: TimeComponents) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Ok(Self {
             regex: compiled_regex,
             fixed_time: value,
-        }
+        })
     }
 }
 
 impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, TimeComponents> for FixedTimeExtractor {
-    fn extract(&self, text: &str, _: &TimeExtractorContext<Tz>) -> Vec<TimeComponents> {
+    fn extract_matches(
+        &self,
+        text: &str,
+        _: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<TimeComponents>> {
         let mut result = Vec::new();
 
-        if self.regex.is_match(text) {
-            result.push(self.fixed_time);
+        if let Some(found) = self.regex.find(text) {
+            result.push(Extraction::new(
+                found.range(),
+                found.as_str(),
+                self.fixed_time,
+            ));
         };
 
         result
@@ -56,27 +67,27 @@ mod test {
 
     test_extract_data! {
         test_extract_no_match_1 {
-            extractor: FixedTimeExtractor::new(r"midday", TimeComponents::of(12, 0, TimeKind::PM)),
+            extractor: FixedTimeExtractor::new(r"midday", TimeComponents::of(12, 0, TimeKind::PM)).unwrap(),
             text: "It is midnight.",
             expected: vec![],
         },
         test_extract_single_match_1 {
-            extractor: FixedTimeExtractor::new(r"noon|midday", TimeComponents::of(12, 0, TimeKind::PM)),
+            extractor: FixedTimeExtractor::new(r"noon|midday", TimeComponents::of(12, 0, TimeKind::PM)).unwrap(),
             text: "I'm free at noon.",
             expected: vec![TimeComponents::of(12, 0, TimeKind::PM)],
         },
         test_extract_single_match_2 {
-            extractor: FixedTimeExtractor::new(r"noon|midday", TimeComponents::of(12, 0, TimeKind::PM)),
+            extractor: FixedTimeExtractor::new(r"noon|midday", TimeComponents::of(12, 0, TimeKind::PM)).unwrap(),
             text: "I'm free at midday.",
             expected: vec![TimeComponents::of(12, 0, TimeKind::PM)],
         },
         test_extract_single_match_3 {
-            extractor: FixedTimeExtractor::new(r"midnight", TimeComponents::of(12, 0, TimeKind::AM)),
+            extractor: FixedTimeExtractor::new(r"midnight", TimeComponents::of(12, 0, TimeKind::AM)).unwrap(),
             text: "It is midnight.",
             expected: vec![TimeComponents::of(12, 0, TimeKind::AM)],
         },
         test_extract_multi_match_1 {
-            extractor: FixedTimeExtractor::new(r"noon", TimeComponents::of(12, 0, TimeKind::Military)),
+            extractor: FixedTimeExtractor::new(r"noon", TimeComponents::of(12, 0, TimeKind::Military)).unwrap(),
             text: "At noon, it is noon.",
             expected: vec![TimeComponents::of(12, 0, TimeKind::Military)],
         },
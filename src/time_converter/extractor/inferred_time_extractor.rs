@@ -0,0 +1,360 @@
+use std::{collections::HashSet, str::FromStr};
+
+use chrono::{Duration, NaiveDate, TimeZone};
+use fancy_regex::{Captures, Regex};
+use itertools::Itertools;
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::{
+        DayPart, ExtractedTime, TimeComponents, TimeExpression, TimeKind, ENGLISH_DAY_PART_WORDS,
+    },
+};
+
+use super::{
+    extractor_error::{check_allowed_groups, check_required_groups, ExtractorResult},
+    time_extractor::resolve,
+    TimeExtractorContext,
+};
+
+/// Extracts bare hours without AM/PM, such as "let's meet at 8", and infers whether
+/// they're AM or PM. A part of the day mentioned anywhere in the message ("this evening")
+/// decides it when present, otherwise the next occurrence after the message was sent is
+/// picked.
+#[derive(Debug)]
+pub struct InferredTimeExtractor {
+    regex: Regex,
+    day_part_regex: Option<Regex>,
+    day_part_words: Vec<(String, DayPart)>,
+}
+
+const HOURS_CAPTURE_NAME: &str = "hours";
+const MINUTES_CAPTURE_NAME: &str = "minutes";
+const DAY_PART_CAPTURE_NAME: &str = "day_part";
+
+const ALLOWED_CAPTURE_GROUPS: [&str; 2] = [HOURS_CAPTURE_NAME, MINUTES_CAPTURE_NAME];
+
+#[allow(dead_code)]
+impl InferredTimeExtractor {
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Self::validate_regex(&compiled_regex)?;
+
+        Ok(Self {
+            regex: compiled_regex,
+            day_part_regex: None,
+            day_part_words: to_owned_words(&ENGLISH_DAY_PART_WORDS),
+        })
+    }
+
+    /// Sets the regex used to find parts of the day ("tonight", "this morning") in the
+    /// message, which take precedence over the time the message was sent.
+    pub fn day_part_hints(mut self, regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        let names: HashSet<&str> = compiled_regex.capture_names().flatten().collect();
+
+        check_required_groups(&names, &[DAY_PART_CAPTURE_NAME])?;
+        check_allowed_groups(&names, &[DAY_PART_CAPTURE_NAME])?;
+
+        self.day_part_regex = Some(compiled_regex);
+        Ok(self)
+    }
+
+    /// Sets the words that name each part of the day in the hints, which are English by
+    /// default.
+    pub fn day_part_words(mut self, words: &[(&str, DayPart)]) -> Self {
+        self.day_part_words = to_owned_words(words);
+        self
+    }
+
+    fn validate_regex(regex: &Regex) -> ExtractorResult<()> {
+        let names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        check_required_groups(&names, &[HOURS_CAPTURE_NAME])?;
+        check_allowed_groups(&names, &ALLOWED_CAPTURE_GROUPS)
+    }
+
+    fn find_day_part(&self, text: &str) -> Option<DayPart> {
+        let captures = self.day_part_regex.as_ref()?.captures(text).ok()??;
+        let phrase = captures
+            .name(DAY_PART_CAPTURE_NAME)
+            .map(|capture| capture.as_str().split_whitespace().join(" ").to_lowercase())?;
+        DayPart::find(&phrase, &self.day_part_words)
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, ExtractedTime> for InferredTimeExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<ExtractedTime>> {
+        let day_part = self.find_day_part(text);
+
+        self.regex
+            .captures_iter(text)
+            .filter_map(|result| result.ok())
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let (hour, minute) = process_captures(&captures)?;
+                let (time, date) = infer(hour, minute, day_part, ctx)?;
+                let instant = resolve(TimeExpression::from(time), date, ctx);
+                Some(Extraction::new(
+                    found.range(),
+                    found.as_str(),
+                    ExtractedTime::Inferred(instant),
+                ))
+            })
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+fn to_owned_words(words: &[(&str, DayPart)]) -> Vec<(String, DayPart)> {
+    words
+        .iter()
+        .map(|(word, day_part)| (word.to_string(), *day_part))
+        .collect()
+}
+
+fn extract_capture<T: FromStr>(captures: &Captures, name: &str) -> Option<T> {
+    captures
+        .name(name)
+        .map(|capture| capture.as_str())
+        .map(|string| string.parse::<T>())
+        .and_then(|result| result.ok())
+}
+
+fn process_captures(captures: &Captures) -> Option<(u32, u32)> {
+    let hour = extract_capture::<u32>(captures, HOURS_CAPTURE_NAME)?;
+    let minute = extract_capture::<u32>(captures, MINUTES_CAPTURE_NAME).unwrap_or(0);
+
+    Some((hour, minute)).filter(|(hour, minute)| (1..=12).contains(hour) && *minute < 60)
+}
+
+/// Picks AM or PM for the hour, and the local date the time falls on.
+fn infer<Tz: TimeZone>(
+    hour: u32,
+    minute: u32,
+    day_part: Option<DayPart>,
+    ctx: &TimeExtractorContext<Tz>,
+) -> Option<(TimeComponents, NaiveDate)> {
+    let date = ctx.date().unwrap_or_else(|| ctx.today());
+
+    if let Some(day_part) = day_part {
+        let time_kind = day_part.time_kind(hour);
+        return Some((TimeComponents::new(hour, minute, time_kind).ok()?, date));
+    }
+
+    // Without a way to tell how far away a different day is, go with the hours people
+    // are most likely to be meeting at.
+    if date != ctx.today() {
+        let time_kind = if (7..=11).contains(&hour) {
+            TimeKind::AM
+        } else {
+            TimeKind::PM
+        };
+        return Some((TimeComponents::new(hour, minute, time_kind).ok()?, date));
+    }
+
+    let msg_time = ctx.message_time().to_24h();
+    let msg_minutes = msg_time.hour() * 60 + msg_time.minute();
+
+    let next_today = [TimeKind::AM, TimeKind::PM]
+        .iter()
+        .filter_map(|time_kind| TimeComponents::new(hour, minute, *time_kind).ok())
+        .find(|time| {
+            let time = time.to_24h();
+            time.hour() * 60 + time.minute() >= msg_minutes
+        });
+
+    match (next_today, ctx.date()) {
+        (Some(time), _) => Some((time, date)),
+        // Both have already passed, so it's the next morning unless the date was written.
+        (None, None) => Some((
+            TimeComponents::new(hour, minute, TimeKind::AM).ok()?,
+            date + Duration::days(1),
+        )),
+        (None, Some(_)) => Some((TimeComponents::new(hour, minute, TimeKind::PM).ok()?, date)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, Timelike, Utc};
+    use chrono_tz::America;
+
+    use crate::time_converter::{extractor::ExtractorError, model::LocalInstant};
+
+    use super::*;
+
+    const AT_REGEX: &str = r"(?i:\b(?:at|@|around)\s+(?P<hours>1[0-2]|0?[1-9])(?::(?P<minutes>[0-5]\d))?(?![\w:]|[.,]\d|\s*(?:[ap]\.?m\b|o'?clock|-?ish|-|–|to\b)))";
+    const DAY_PART_REGEX: &str = r"(?i:\b(?P<day_part>tonight|(?:this|tomorrow|in\s+the)\s+(?:morning|afternoon|evening))\b)";
+
+    #[test]
+    fn test_missing_capture_groups_1() {
+        let error = InferredTimeExtractor::new(r"at (?P<minutes>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::MissingCaptureGroups { missing } if missing == vec!["hours"]
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_capture_groups_1() {
+        let error =
+            InferredTimeExtractor::new(r"at (?P<hours>\d+)(?P<time_kind>[ap]m)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::UnexpectedCaptureGroups { unexpected, .. } if unexpected == vec!["time_kind"]
+        ));
+    }
+
+    #[test]
+    fn test_missing_day_part_capture_group() {
+        let error = InferredTimeExtractor::new(AT_REGEX)
+            .unwrap()
+            .day_part_hints(r"tonight")
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::MissingCaptureGroups { missing } if missing == vec!["day_part"]
+        ));
+    }
+
+    fn instant(hour: u32, minute: u32, day_offset: i64) -> DateTime<Utc> {
+        let date = NaiveDate::from_ymd(2021, 3, 4) + Duration::days(day_offset);
+        DateTime::from_utc(date.and_hms(hour, minute, 0), Utc)
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{text: $input_text:expr, msg_time: $msg_time:expr, date: $date:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = InferredTimeExtractor::new(AT_REGEX).unwrap().day_part_hints(DAY_PART_REGEX).unwrap();
+                let (hour, minute) = $msg_time;
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(hour, minute, TimeKind::Military))
+                    .with_message_timestamp(instant(hour, minute, 0));
+                let ctx = match $date {
+                    Some(date) => ctx.with_date(date),
+                    None => ctx,
+                };
+                let actual: Vec<ExtractedTime> = extractor.extract($input_text, &ctx);
+                let expected: Vec<ExtractedTime> = $expected.into_iter().map(|instant: DateTime<Utc>| ExtractedTime::Inferred(instant.into())).collect();
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_morning_message {
+            text: "let's meet at 10",
+            msg_time: (8, 0),
+            date: None::<NaiveDate>,
+            expected: vec![instant(10, 0, 0)],
+        },
+        test_extract_afternoon_message {
+            text: "let's meet at 8",
+            msg_time: (14, 30),
+            date: None::<NaiveDate>,
+            expected: vec![instant(20, 0, 0)],
+        },
+        test_extract_late_message {
+            text: "let's meet at 8:30",
+            msg_time: (22, 0),
+            date: None::<NaiveDate>,
+            expected: vec![instant(8, 30, 1)],
+        },
+        test_extract_late_message_with_date {
+            text: "today at 8",
+            msg_time: (22, 0),
+            date: Some(NaiveDate::from_ymd(2021, 3, 4)),
+            expected: vec![instant(20, 0, 0)],
+        },
+        test_extract_other_date {
+            text: "see you friday at 9 and saturday at 2",
+            msg_time: (22, 0),
+            date: Some(NaiveDate::from_ymd(2021, 3, 5)),
+            expected: vec![
+                DateTime::from_utc(NaiveDate::from_ymd(2021, 3, 5).and_hms(9, 0, 0), Utc),
+                DateTime::from_utc(NaiveDate::from_ymd(2021, 3, 5).and_hms(14, 0, 0), Utc),
+            ],
+        },
+        test_extract_day_part_hint {
+            text: "This evening, around 7?",
+            msg_time: (6, 0),
+            date: None::<NaiveDate>,
+            expected: vec![instant(19, 0, 0)],
+        },
+        test_extract_morning_hint {
+            text: "tomorrow morning at 11",
+            msg_time: (15, 0),
+            date: None::<NaiveDate>,
+            expected: vec![instant(11, 0, 0)],
+        },
+        test_extract_requires_trigger_word {
+            text: "I have 8 apples",
+            msg_time: (8, 0),
+            date: None::<NaiveDate>,
+            expected: Vec::<DateTime<Utc>>::new(),
+        },
+        test_extract_ignores_explicit_time_kind {
+            text: "at 8pm or at 9 a.m.",
+            msg_time: (8, 0),
+            date: None::<NaiveDate>,
+            expected: Vec::<DateTime<Utc>>::new(),
+        },
+        test_extract_ignores_ranges_and_decimals {
+            text: "at 8-10 or at 3.5",
+            msg_time: (8, 0),
+            date: None::<NaiveDate>,
+            expected: Vec::<DateTime<Utc>>::new(),
+        },
+    }
+
+    macro_rules! test_extract_pinned_data {
+        ($($name:ident{msg_timestamp: $msg_timestamp:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = InferredTimeExtractor::new(AT_REGEX).unwrap();
+                let msg_timestamp: DateTime<Utc> = $msg_timestamp;
+                let msg_time = msg_timestamp.with_timezone(&America::New_York);
+                let ctx = TimeExtractorContext::new(America::New_York, TimeComponents::of(msg_time.hour(), msg_time.minute(), TimeKind::Military))
+                    .with_message_timestamp(msg_timestamp);
+                let actual: Vec<ExtractedTime> = extractor.extract($input_text, &ctx);
+                let expected: Vec<ExtractedTime> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    // Daylight saving time starts in New York at 2am on March 14th, 2021, and ends at 2am on
+    // November 7th, 2021.
+    test_extract_pinned_data! {
+        test_extract_pinned_skipped {
+            // 1am EST
+            msg_timestamp: Utc.ymd(2021, 3, 14).and_hms(6, 0, 0),
+            text: "let's meet at 2:30",
+            expected: vec![ExtractedTime::Inferred(LocalInstant::Skipped {
+                earlier: Utc.ymd(2021, 3, 14).and_hms(6, 30, 0),
+                later: Utc.ymd(2021, 3, 14).and_hms(7, 30, 0),
+            })],
+        },
+        test_extract_pinned_repeated {
+            // 12am EDT
+            msg_timestamp: Utc.ymd(2021, 11, 7).and_hms(4, 0, 0),
+            text: "let's meet at 1:30",
+            expected: vec![ExtractedTime::Inferred(LocalInstant::Repeated {
+                earlier: Utc.ymd(2021, 11, 7).and_hms(5, 30, 0),
+                later: Utc.ymd(2021, 11, 7).and_hms(6, 30, 0),
+            })],
+        },
+    }
+}
@@ -1,10 +1,35 @@
 mod current_time_extractor;
+mod date_extractor;
+mod discord_timestamp_extractor;
+mod dynamic_date_extractor;
 mod dynamic_time_extractor;
+mod extractor_error;
+mod fixed_date_extractor;
 mod fixed_time_extractor;
+mod inferred_time_extractor;
+mod overlap_resolver;
+mod phrase_time_extractor;
+mod prefilter;
+mod relative_time_extractor;
 mod time_extractor;
 mod time_extractor_context;
+mod time_range_extractor;
+mod time_zone_aliases;
+mod user;
 
 pub use current_time_extractor::CurrentTimeExtractor;
+pub use discord_timestamp_extractor::DiscordTimestampExtractor;
+pub use dynamic_date_extractor::DynamicDateExtractor;
 pub use dynamic_time_extractor::DynamicTimeExtractor;
+pub use extractor_error::{ExtractorError, ExtractorResult};
+pub use fixed_date_extractor::FixedDateExtractor;
 pub use fixed_time_extractor::FixedTimeExtractor;
+pub use inferred_time_extractor::InferredTimeExtractor;
+pub use overlap_resolver::OverlapResolver;
+pub use phrase_time_extractor::PhraseTimeExtractor;
+pub use prefilter::Prefilter;
+pub use relative_time_extractor::RelativeTimeExtractor;
 pub use time_extractor_context::TimeExtractorContext;
+pub use time_range_extractor::TimeRangeExtractor;
+pub use time_zone_aliases::{AmbiguousZonePolicy, TIME_ZONE_SUFFIX_PATTERN};
+pub use user::{MentionedUserExtractor, UserExtractorContext};
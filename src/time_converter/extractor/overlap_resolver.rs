@@ -0,0 +1,283 @@
+use std::{cmp::Reverse, fmt, ops::Range};
+
+use itertools::Itertools;
+
+use crate::extractor::{Extraction, Extractor};
+
+use super::Prefilter;
+
+/// Combines extractors so that each part of a text has at most one interpretation.
+///
+/// When extractions overlap, the one from the extractor with the higher priority wins. Between
+/// equal priorities the longer extraction wins, and after that the extractor added first.
+///
+/// With a prefilter, the extractors only search the parts of the text the prefilter finds.
+pub struct OverlapResolver<C, R> {
+    extractors: Vec<(u32, Box<dyn Extractor<C, R>>)>,
+    prefilter: Option<Prefilter>,
+}
+
+#[allow(dead_code)]
+impl<C, R> OverlapResolver<C, R> {
+    pub fn new() -> Self {
+        Self {
+            extractors: Vec::new(),
+            prefilter: None,
+        }
+    }
+
+    pub fn with(mut self, priority: u32, extractor: Box<dyn Extractor<C, R>>) -> Self {
+        self.extractors.push((priority, extractor));
+        self
+    }
+
+    pub fn prefilter(mut self, prefilter: Prefilter) -> Self {
+        self.prefilter = Some(prefilter);
+        self
+    }
+
+    fn resolve(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        // Without a prefilter, the whole text is the only region.
+        #[allow(clippy::single_range_in_vec_init)]
+        let regions = match &self.prefilter {
+            Some(prefilter) => prefilter.candidate_regions(text),
+            None => vec![0..text.len()],
+        };
+
+        let candidates = regions
+            .into_iter()
+            .flat_map(|region| {
+                let offset = region.start;
+                let region_text = &text[region];
+                self.extractors
+                    .iter()
+                    .flat_map(move |(priority, extractor)| {
+                        extractor
+                            .as_ref()
+                            .extract_matches(region_text, ctx)
+                            .into_iter()
+                            .map(move |extraction| (*priority, extraction.offset(offset)))
+                    })
+            })
+            .sorted_by_key(|(priority, extraction)| {
+                let span = extraction.span();
+                (Reverse(*priority), Reverse(span.len()), span.start)
+            });
+
+        let mut resolved: Vec<Extraction<R>> = Vec::new();
+        for (_, extraction) in candidates {
+            let span = extraction.span();
+            if !resolved
+                .iter()
+                .any(|existing| overlaps(&existing.span(), &span))
+            {
+                resolved.push(extraction);
+            }
+        }
+
+        resolved.sort_by_key(|extraction| extraction.span().start);
+        resolved
+    }
+}
+
+impl<C, R> Default for OverlapResolver<C, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, R> fmt::Debug for OverlapResolver<C, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverlapResolver")
+            .field("extractors", &self.extractors)
+            .field("prefilter", &self.prefilter)
+            .finish()
+    }
+}
+
+impl<C, R> Extractor<C, R> for OverlapResolver<C, R> {
+    fn extract_matches(&self, text: &str, ctx: &C) -> Vec<Extraction<R>> {
+        self.resolve(text, ctx)
+    }
+}
+
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    use crate::time_converter::{
+        extractor::{
+            DiscordTimestampExtractor, DynamicTimeExtractor, FixedTimeExtractor,
+            InferredTimeExtractor, PhraseTimeExtractor, TimeExtractorContext, TimeRangeExtractor,
+        },
+        model::{ExtractedTime, TimeComponents, TimeKind},
+    };
+
+    use super::*;
+
+    const AM_PM_RANGE_REGEX: &str = r"(?i:(?<![\w:.\-])(?:from\s+)?(?P<start_hours>1[012]|0?[1-9])(?::(?P<start_minutes>[0-5]\d))?\s*(?P<start_time_kind>[ap]m)?\s*(?:-|–|—|~|to|until|till|til|through|thru)\s*(?P<end_hours>1[012]|0?[1-9])(?::(?P<end_minutes>[0-5]\d))?\s*(?P<end_time_kind>[ap]m)(?!\w))";
+    const AM_PM_REGEX: &str = r"(?i:(?<!\w)(?P<hours>(?:1[012])|(?:0?[123456789]))\s*(?::\s*(?P<minutes>(?:[12345]\d)|(?:0\d)))?\s*(?P<time_kind>[ap]m)(?!\w))";
+    const OFFSET_PHRASE_REGEX: &str = r"(?i:(?<!\bfrom\s)\b(?P<offset>half|(?:a\s+)?quarter|twenty[\s-]five|twenty|ten|five|[1-5]?\d(?=\s+minutes?\b))\s+(?:minutes?\s+)?(?P<direction>past|after|to|till|before)\s+(?P<hours>1[0-2]|0?[1-9]|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve)(?:\s*o'?clock)?(?:\s*(?P<time_kind>[ap]m)(?!\w))?(?:\s+(?P<day_part>tonight|at\s+night|(?:this|tomorrow|in\s+the)\s+(?:morning|afternoon|evening)))?\b)";
+    const DAY_PART_PHRASE_REGEX: &str = r"(?i:\b(?P<day_part>tonight|at\s+night|(?:this|tomorrow|in\s+the)\s+(?:morning|afternoon|evening))\s+(?:at\s+|around\s+)?(?P<hours>1[0-2]|0?[1-9]|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve)(?::(?P<minutes>[0-5]\d))?(?:\s*o'?clock|\s*-?ish)?(?![\w:]))";
+    const INFERRED_REGEX: &str = r"(?i:\b(?:at|@|around)\s+(?P<hours>1[0-2]|0?[1-9])(?::(?P<minutes>[0-5]\d))?(?![\w:]|[.,]\d|\s*(?:[ap]\.?m\b|o'?clock|-?ish|-|–|to\b)))";
+
+    type TimeResolver = OverlapResolver<TimeExtractorContext<Utc>, ExtractedTime>;
+
+    fn resolver() -> TimeResolver {
+        OverlapResolver::new()
+            .with(4, Box::new(DiscordTimestampExtractor::new()))
+            .with(
+                3,
+                Box::new(TimeRangeExtractor::new(AM_PM_RANGE_REGEX).unwrap()),
+            )
+            .with(
+                2,
+                Box::new(PhraseTimeExtractor::new(OFFSET_PHRASE_REGEX).unwrap()),
+            )
+            .with(
+                2,
+                Box::new(PhraseTimeExtractor::new(DAY_PART_PHRASE_REGEX).unwrap()),
+            )
+            .with(
+                1,
+                Box::new(
+                    FixedTimeExtractor::new(
+                        r"(?i:midnight)",
+                        TimeComponents::of(0, 0, TimeKind::Military),
+                    )
+                    .unwrap(),
+                ),
+            )
+            .with(1, Box::new(DynamicTimeExtractor::new(AM_PM_REGEX).unwrap()))
+            .with(
+                0,
+                Box::new(InferredTimeExtractor::new(INFERRED_REGEX).unwrap()),
+            )
+    }
+
+    fn prefiltered_resolver() -> TimeResolver {
+        resolver().prefilter(Prefilter::new([r"\d", r"(?i:midnight|three)"]).unwrap())
+    }
+
+    fn instant(hour: u32, minute: u32) -> DateTime<Utc> {
+        DateTime::from_utc(
+            NaiveDate::from_ymd(2021, 3, 4).and_hms(hour, minute, 0),
+            Utc,
+        )
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(overlaps(&(0..5), &(4..6)));
+        assert!(overlaps(&(2..3), &(0..5)));
+        assert!(!overlaps(&(0..5), &(5..6)));
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(9, 0, TimeKind::Military))
+                    .with_date(NaiveDate::from_ymd(2021, 3, 4))
+                    .with_message_timestamp(instant(9, 0));
+                let actual: Vec<(Range<usize>, ExtractedTime)> = resolver()
+                    .extract_matches($input_text, &ctx)
+                    .into_iter()
+                    .map(|extraction| (extraction.span(), *extraction.value()))
+                    .collect();
+                let expected: Vec<(Range<usize>, ExtractedTime)> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_no_overlap {
+            text: "5pm or midnight",
+            expected: vec![
+                (0..3, ExtractedTime::Instant(instant(17, 0).into())),
+                (7..15, ExtractedTime::Instant(instant(0, 0).into())),
+            ],
+        },
+        test_extract_range_over_bounds {
+            text: "5am-6pm",
+            expected: vec![
+                (0..7, ExtractedTime::Range { start: instant(5, 0).into(), end: instant(18, 0).into() }),
+            ],
+        },
+        test_extract_range_and_separate_time {
+            text: "5-7pm, or 9pm",
+            expected: vec![
+                (0..5, ExtractedTime::Range { start: instant(17, 0).into(), end: instant(19, 0).into() }),
+                (10..13, ExtractedTime::Instant(instant(21, 0).into())),
+            ],
+        },
+        test_extract_phrase_over_time {
+            text: "half past 3pm",
+            expected: vec![(0..13, ExtractedTime::Instant(instant(15, 30).into()))],
+        },
+        test_extract_phrase_over_inferred_time {
+            text: "tonight at 8",
+            expected: vec![(0..12, ExtractedTime::Instant(instant(20, 0).into()))],
+        },
+        test_extract_inferred_time {
+            text: "let's meet at 8",
+            expected: vec![(11..15, ExtractedTime::Inferred(instant(20, 0).into()))],
+        },
+        test_extract_discord_timestamp_and_time {
+            text: "<t:1614891600:t> 9pm",
+            expected: vec![
+                (0..16, ExtractedTime::Instant(instant(21, 0).into())),
+                (17..20, ExtractedTime::Instant(instant(21, 0).into())),
+            ],
+        },
+    }
+
+    macro_rules! test_extract_prefiltered_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(9, 0, TimeKind::Military))
+                    .with_date(NaiveDate::from_ymd(2021, 3, 4))
+                    .with_message_timestamp(instant(9, 0));
+                let actual: Vec<(Range<usize>, ExtractedTime)> = prefiltered_resolver()
+                    .extract_matches($input_text, &ctx)
+                    .into_iter()
+                    .map(|extraction| (extraction.span(), *extraction.value()))
+                    .collect();
+                let expected: Vec<(Range<usize>, ExtractedTime)> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_prefiltered_data! {
+        test_extract_prefiltered_nothing {
+            text: "no times here",
+            expected: vec![],
+        },
+        test_extract_prefiltered_spans {
+            text: "hello\nsee you at 5pm\nbye\nor midnight",
+            expected: vec![
+                (17..20, ExtractedTime::Instant(instant(17, 0).into())),
+                (28..36, ExtractedTime::Instant(instant(0, 0).into())),
+            ],
+        },
+        test_extract_prefiltered_keyword {
+            text: "half past three this evening",
+            expected: vec![(0..28, ExtractedTime::Instant(instant(15, 30).into()))],
+        },
+        test_extract_prefiltered_missing_keyword {
+            text: "half past four this evening",
+            expected: vec![],
+        },
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashSet;
+
+use chrono::TimeZone;
+use fancy_regex::{Captures, Regex};
+use itertools::Itertools;
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::{DayPart, TimeComponents, TimeKind, ENGLISH_DAY_PART_WORDS},
+};
+
+use super::{
+    extractor_error::{
+        check_allowed_groups, check_required_groups, invalid_groups, ExtractorResult,
+    },
+    TimeExtractorContext,
+};
+
+/// Extracts colloquial time phrases such as "half past 3pm", "quarter to 9 tonight",
+/// "8ish this evening" or "tonight at 8". Phrases are only extracted when AM/PM can be
+/// determined, either from an explicit AM/PM or from a part of the day.
+#[derive(Debug)]
+pub struct PhraseTimeExtractor {
+    regex: Regex,
+}
+
+const HOURS_CAPTURE_NAME: &str = "hours";
+const MINUTES_CAPTURE_NAME: &str = "minutes";
+const OFFSET_CAPTURE_NAME: &str = "offset";
+const DIRECTION_CAPTURE_NAME: &str = "direction";
+const TIME_KIND_CAPTURE_NAME: &str = "time_kind";
+const DAY_PART_CAPTURE_NAME: &str = "day_part";
+
+const ALLOWED_CAPTURE_GROUPS: [&str; 6] = [
+    HOURS_CAPTURE_NAME,
+    MINUTES_CAPTURE_NAME,
+    OFFSET_CAPTURE_NAME,
+    DIRECTION_CAPTURE_NAME,
+    TIME_KIND_CAPTURE_NAME,
+    DAY_PART_CAPTURE_NAME,
+];
+
+const NUMBER_WORDS: [&str; 12] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+
+#[allow(dead_code)]
+impl PhraseTimeExtractor {
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Self::validate_regex(&compiled_regex)?;
+
+        Ok(Self {
+            regex: compiled_regex,
+        })
+    }
+
+    fn validate_regex(regex: &Regex) -> ExtractorResult<()> {
+        let names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        check_required_groups(&names, &[HOURS_CAPTURE_NAME])?;
+
+        if names.contains(OFFSET_CAPTURE_NAME) != names.contains(DIRECTION_CAPTURE_NAME) {
+            return Err(invalid_groups(
+                &names,
+                "both or neither of the \"offset\" and \"direction\" named capture groups",
+            ));
+        }
+
+        check_allowed_groups(&names, &ALLOWED_CAPTURE_GROUPS)
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, TimeComponents> for PhraseTimeExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        _ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<TimeComponents>> {
+        self.regex
+            .captures_iter(text)
+            .filter_map(|result| result.ok())
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let time = process_captures(&captures)?;
+                Some(Extraction::new(found.range(), found.as_str(), time))
+            })
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+fn capture<'t>(captures: &Captures<'t>, name: &str) -> Option<String> {
+    captures
+        .name(name)
+        .map(|capture| capture.as_str().split_whitespace().join(" ").to_lowercase())
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    text.parse::<u32>().ok().or_else(|| {
+        NUMBER_WORDS
+            .iter()
+            .position(|word| *word == text)
+            .map(|index| index as u32 + 1)
+    })
+}
+
+fn parse_offset_minutes(text: &str) -> Option<u32> {
+    let minutes = match text {
+        "half" => 30,
+        "quarter" | "a quarter" => 15,
+        "twenty five" | "twenty-five" => 25,
+        "twenty" => 20,
+        other => parse_number(other)?,
+    };
+
+    Some(minutes).filter(|minutes| (1..60).contains(minutes))
+}
+
+/// Works out AM/PM from a part of the day, e.g. "this morning" or "tonight".
+pub(super) fn day_part_time_kind(day_part: &str, hour: u32) -> Option<TimeKind> {
+    DayPart::find(day_part, &ENGLISH_DAY_PART_WORDS).map(|day_part| day_part.time_kind(hour))
+}
+
+fn process_captures(captures: &Captures) -> Option<TimeComponents> {
+    let hour = parse_number(&capture(captures, HOURS_CAPTURE_NAME)?)?;
+    let minute = match capture(captures, MINUTES_CAPTURE_NAME) {
+        Some(minutes) => minutes.parse::<u32>().ok()?,
+        None => 0,
+    };
+
+    let time_kind = match capture(captures, TIME_KIND_CAPTURE_NAME).as_deref() {
+        Some("am") => TimeKind::AM,
+        Some("pm") => TimeKind::PM,
+        Some(_) => return None,
+        None => day_part_time_kind(&capture(captures, DAY_PART_CAPTURE_NAME)?, hour)?,
+    };
+
+    let time = TimeComponents::new(hour, minute, time_kind).ok()?.to_24h();
+
+    let offset = capture(captures, OFFSET_CAPTURE_NAME);
+    let direction = capture(captures, DIRECTION_CAPTURE_NAME);
+    let minutes_of_day = match (offset, direction.as_deref()) {
+        (Some(offset), Some("past")) | (Some(offset), Some("after")) => {
+            time.hour() * 60 + parse_offset_minutes(&offset)?
+        }
+        // Only the time is extracted, so a time before midnight can't be moved to the day
+        // before, and the phrase is left out instead.
+        (Some(offset), Some(_)) => {
+            (time.hour() * 60).checked_sub(parse_offset_minutes(&offset)?)?
+        }
+        _ => time.hour() * 60 + time.minute(),
+    };
+
+    TimeComponents::new(minutes_of_day / 60, minutes_of_day % 60, TimeKind::Military).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+
+    use crate::time_converter::extractor::ExtractorError;
+
+    use super::*;
+
+    const NUMBER: &str =
+        r"1[0-2]|0?[1-9]|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve";
+    const DAY_PART: &str =
+        r"tonight|at\s+night|(?:this|tomorrow|in\s+the)\s+(?:morning|afternoon|evening)";
+
+    fn offset_regex() -> String {
+        format!(
+            r"(?i:(?<!\bfrom\s)\b(?P<offset>half|(?:a\s+)?quarter|twenty[\s-]five|twenty|ten|five|[1-5]?\d(?=\s+minutes?\b))\s+(?:minutes?\s+)?(?P<direction>past|after|to|till|before)\s+(?P<hours>{})(?:\s*o'?clock)?(?:\s*(?P<time_kind>[ap]m)(?!\w))?(?:\s+(?P<day_part>{}))?\b)",
+            NUMBER, DAY_PART
+        )
+    }
+
+    fn approximate_regex() -> String {
+        format!(
+            r"(?i:(?<![\w:.])(?P<hours>{})(?::(?P<minutes>[0-5]\d))?\s*(?P<time_kind>[ap]m)?\s*(?:o'?clock|-?ish)(?:\s+(?P<day_part>{}))?\b)",
+            NUMBER, DAY_PART
+        )
+    }
+
+    fn day_part_first_regex() -> String {
+        format!(
+            r"(?i:\b(?P<day_part>{})\s+(?:at\s+|around\s+)?(?P<hours>{})(?::(?P<minutes>[0-5]\d))?(?:\s*o'?clock|\s*-?ish)?(?![\w:]))",
+            DAY_PART, NUMBER
+        )
+    }
+
+    fn day_part_last_regex() -> String {
+        format!(
+            r"(?i:(?<![\w:.])(?P<hours>{})(?::(?P<minutes>[0-5]\d))?(?:\s*o'?clock|\s*-?ish)?\s+(?P<day_part>{})\b)",
+            NUMBER, DAY_PART
+        )
+    }
+
+    #[test]
+    fn test_missing_capture_groups_1() {
+        let error =
+            PhraseTimeExtractor::new(r"(?P<offset>half) past (?P<minutes>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::MissingCaptureGroups { missing } if missing == vec!["hours"]
+        ));
+    }
+
+    #[test]
+    fn test_missing_capture_groups_2() {
+        let error = PhraseTimeExtractor::new(r"(?P<offset>half) past (?P<hours>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::InvalidCaptureGroups { found, .. } if found == vec!["hours", "offset"]
+        ));
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{regex: $input_regex:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = PhraseTimeExtractor::new(&$input_regex).unwrap();
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM));
+                let actual: Vec<TimeComponents> = extractor.extract($input_text, &ctx);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_half_past {
+            regex: offset_regex(),
+            text: "half past 3pm",
+            expected: vec![TimeComponents::of(15, 30, TimeKind::Military)],
+        },
+        test_extract_quarter_past_words {
+            regex: offset_regex(),
+            text: "Quarter past nine in the morning",
+            expected: vec![TimeComponents::of(9, 15, TimeKind::Military)],
+        },
+        test_extract_quarter_to {
+            regex: offset_regex(),
+            text: "quarter to 9 tonight",
+            expected: vec![TimeComponents::of(20, 45, TimeKind::Military)],
+        },
+        test_extract_quarter_to_noon {
+            regex: offset_regex(),
+            text: "a quarter to 12pm",
+            expected: vec![TimeComponents::of(11, 45, TimeKind::Military)],
+        },
+        test_extract_quarter_to_midnight {
+            regex: offset_regex(),
+            text: "quarter to 12am",
+            expected: vec![],
+        },
+        test_extract_minutes_to_o_clock {
+            regex: offset_regex(),
+            text: "10 minutes to 6 o'clock this evening",
+            expected: vec![TimeComponents::of(17, 50, TimeKind::Military)],
+        },
+        test_extract_numbers_need_minutes {
+            regex: offset_regex(),
+            text: "5 to 9 tonight",
+            expected: vec![],
+        },
+        test_extract_range_of_words {
+            regex: offset_regex(),
+            text: "from five to nine tonight",
+            expected: vec![],
+        },
+        test_extract_twenty_five_past {
+            regex: offset_regex(),
+            text: "twenty-five past 4 pm",
+            expected: vec![TimeComponents::of(16, 25, TimeKind::Military)],
+        },
+        test_extract_offset_without_time_kind {
+            regex: offset_regex(),
+            text: "half past 3",
+            expected: vec![],
+        },
+        test_extract_o_clock {
+            regex: approximate_regex(),
+            text: "8 o'clock tonight",
+            expected: vec![TimeComponents::of(20, 0, TimeKind::Military)],
+        },
+        test_extract_ish_with_time_kind {
+            regex: approximate_regex(),
+            text: "8pmish?",
+            expected: vec![TimeComponents::of(20, 0, TimeKind::Military)],
+        },
+        test_extract_ish_with_day_part {
+            regex: approximate_regex(),
+            text: "7:30-ish this evening",
+            expected: vec![TimeComponents::of(19, 30, TimeKind::Military)],
+        },
+        test_extract_ish_without_time_kind {
+            regex: approximate_regex(),
+            text: "8ish",
+            expected: vec![],
+        },
+        test_extract_day_part_first {
+            regex: day_part_first_regex(),
+            text: "tonight at 8",
+            expected: vec![TimeComponents::of(20, 0, TimeKind::Military)],
+        },
+        test_extract_day_part_first_with_minutes {
+            regex: day_part_first_regex(),
+            text: "This morning at 10:45 I was asleep",
+            expected: vec![TimeComponents::of(10, 45, TimeKind::Military)],
+        },
+        test_extract_day_part_first_after_midnight {
+            regex: day_part_first_regex(),
+            text: "tonight at 12",
+            expected: vec![TimeComponents::of(0, 0, TimeKind::Military)],
+        },
+        test_extract_day_part_last {
+            regex: day_part_last_regex(),
+            text: "see you at 3 this afternoon",
+            expected: vec![TimeComponents::of(15, 0, TimeKind::Military)],
+        },
+        test_extract_day_part_last_at_night {
+            regex: day_part_last_regex(),
+            text: "it was 2 at night",
+            expected: vec![TimeComponents::of(2, 0, TimeKind::Military)],
+        },
+        test_extract_day_part_ignores_other_numbers {
+            regex: day_part_last_regex(),
+            text: "version 1.2 tonight",
+            expected: vec![],
+        },
+    }
+}
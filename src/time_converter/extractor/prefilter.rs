@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+use itertools::Itertools;
+use regex::Regex;
+
+use super::extractor_error::ExtractorResult;
+
+/// Finds the parts of a text that could contain a time, so extractors only have to search
+/// those parts. Most messages don't mention a time at all, and a single pass over them is a
+/// lot cheaper than running every extractor's regex.
+///
+/// The patterns should match something every time contains, such as a digit or a word for a
+/// time. The parts searched are the lines with a match, since times don't span lines.
+#[derive(Debug, Clone)]
+pub struct Prefilter {
+    regex: Regex,
+}
+
+#[allow(dead_code)]
+impl Prefilter {
+    pub fn new<S: AsRef<str>>(patterns: impl IntoIterator<Item = S>) -> ExtractorResult<Self> {
+        let combined = patterns
+            .into_iter()
+            .map(|pattern| format!("(?:{})", pattern.as_ref()))
+            .join("|");
+
+        Ok(Self {
+            regex: Regex::new(&combined)?,
+        })
+    }
+
+    /// The byte ranges of the lines that match a pattern, where consecutive lines are merged
+    /// into one range.
+    pub fn candidate_regions(&self, text: &str) -> Vec<Range<usize>> {
+        let mut regions: Vec<Range<usize>> = Vec::new();
+
+        for found in self.regex.find_iter(text) {
+            let start = text[..found.start()]
+                .rfind('\n')
+                .map_or(0, |index| index + 1);
+            let end = text[found.end()..]
+                .find('\n')
+                .map_or(text.len(), |index| found.end() + index);
+
+            match regions.last_mut() {
+                Some(last) if start <= last.end + 1 => last.end = last.end.max(end),
+                _ => regions.push(start..end),
+            }
+        }
+
+        regions
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PATTERNS: [&str; 2] = [r"\d", r"(?i:noon|midnight)"];
+
+    macro_rules! test_candidate_regions_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let prefilter = Prefilter::new(PATTERNS).unwrap();
+                let actual: Vec<&str> = prefilter
+                    .candidate_regions($input_text)
+                    .into_iter()
+                    .map(|region| &$input_text[region])
+                    .collect();
+                let expected: Vec<&str> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_candidate_regions_data! {
+        test_candidate_regions_none {
+            text: "nothing to see here",
+            expected: vec![],
+        },
+        test_candidate_regions_single_line {
+            text: "let's meet at 5pm",
+            expected: vec!["let's meet at 5pm"],
+        },
+        test_candidate_regions_keyword {
+            text: "lunch at noon?",
+            expected: vec!["lunch at noon?"],
+        },
+        test_candidate_regions_several_matches_on_a_line {
+            text: "from 3pm to 5pm",
+            expected: vec!["from 3pm to 5pm"],
+        },
+        test_candidate_regions_lines {
+            text: "hello\nat 5pm\nbye\nor midnight\n",
+            expected: vec!["at 5pm", "or midnight"],
+        },
+        test_candidate_regions_consecutive_lines {
+            text: "hello\nat 5pm\nor midnight\nbye",
+            expected: vec!["at 5pm\nor midnight"],
+        },
+        test_candidate_regions_last_line {
+            text: "hello\n10:30",
+            expected: vec!["10:30"],
+        },
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(Prefilter::new([r"(\d"]).is_err());
+    }
+}
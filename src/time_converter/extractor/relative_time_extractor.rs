@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use fancy_regex::{Captures, Regex};
+use itertools::Itertools;
+
+use crate::extractor::{Extraction, Extractor};
+
+use super::{
+    extractor_error::{check_allowed_groups, invalid_groups, ExtractorResult},
+    TimeExtractorContext,
+};
+
+/// Extracts times written relative to when the message was sent, such as "in 2 hours" or
+/// "in 1h30m".
+#[derive(Debug)]
+pub struct RelativeTimeExtractor {
+    regex: Regex,
+}
+
+const DAYS_CAPTURE_NAME: &str = "days";
+const HOURS_CAPTURE_NAME: &str = "hours";
+const MINUTES_CAPTURE_NAME: &str = "minutes";
+
+const ALLOWED_CAPTURE_GROUPS: [&str; 3] =
+    [DAYS_CAPTURE_NAME, HOURS_CAPTURE_NAME, MINUTES_CAPTURE_NAME];
+
+#[allow(dead_code)]
+impl RelativeTimeExtractor {
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Self::validate_regex(&compiled_regex)?;
+
+        Ok(Self {
+            regex: compiled_regex,
+        })
+    }
+
+    fn validate_regex(regex: &Regex) -> ExtractorResult<()> {
+        let names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        if names.is_empty() {
+            return Err(invalid_groups(
+                &names,
+                "at least one of the \"days\", \"hours\" and \"minutes\" named capture groups",
+            ));
+        }
+
+        check_allowed_groups(&names, &ALLOWED_CAPTURE_GROUPS)
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, DateTime<Utc>> for RelativeTimeExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<DateTime<Utc>>> {
+        let msg_timestamp = ctx.now();
+
+        self.regex
+            .captures_iter(text)
+            .filter_map(|result| result.ok())
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let offset = process_captures(&captures)?;
+                Some(Extraction::new(
+                    found.range(),
+                    found.as_str(),
+                    msg_timestamp + offset,
+                ))
+            })
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+fn parse_amount(text: &str) -> Option<f64> {
+    let normalized = text.split_whitespace().join(" ").to_lowercase();
+
+    match normalized.as_str() {
+        "a" | "an" | "one" => Some(1.0),
+        "half a" | "half an" => Some(0.5),
+        number => number.replace(',', ".").parse::<f64>().ok(),
+    }
+}
+
+fn extract_amount(captures: &Captures, name: &str) -> Option<f64> {
+    captures
+        .name(name)
+        .and_then(|capture| parse_amount(capture.as_str()))
+}
+
+fn process_captures(captures: &Captures) -> Option<Duration> {
+    let days = extract_amount(captures, DAYS_CAPTURE_NAME);
+    let hours = extract_amount(captures, HOURS_CAPTURE_NAME);
+    let minutes = extract_amount(captures, MINUTES_CAPTURE_NAME);
+
+    if days.is_none() && hours.is_none() && minutes.is_none() {
+        return None;
+    }
+
+    let total_minutes =
+        days.unwrap_or(0.0) * 24.0 * 60.0 + hours.unwrap_or(0.0) * 60.0 + minutes.unwrap_or(0.0);
+
+    // Anything further out than a year is much more likely to be a misread than a time.
+    if !(0.0..=365.0 * 24.0 * 60.0).contains(&total_minutes) {
+        return None;
+    }
+
+    Some(Duration::seconds((total_minutes * 60.0).round() as i64))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::time_converter::{
+        clock::FixedClock,
+        extractor::ExtractorError,
+        model::{TimeComponents, TimeKind},
+    };
+
+    use super::*;
+
+    const RELATIVE_REGEX: &str = r"(?i:\bin\s+(?:(?P<days>\d+|an?|one)\s*d(?:ays?)?(?![a-z])\s*(?:,|and)?\s*)?(?:(?P<hours>\d+(?:[.,]\d+)?|an?|one|half\s+an?)\s*h(?:(?:ou)?rs?)?(?![a-z])\s*(?:,|and)?\s*)?(?:(?P<minutes>\d+)\s*m(?:in(?:ute)?s?)?(?![a-z]))?)";
+
+    #[test]
+    fn test_missing_capture_groups_1() {
+        let error = RelativeTimeExtractor::new(r"in \d+ hours").unwrap_err();
+
+        assert!(matches!(error, ExtractorError::InvalidCaptureGroups { .. }));
+    }
+
+    #[test]
+    fn test_unexpected_capture_groups_1() {
+        let error = RelativeTimeExtractor::new(r"in (?P<hours>\d+) hours (?P<seconds>\d+) seconds")
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::UnexpectedCaptureGroups { unexpected, .. } if unexpected == vec!["seconds"]
+        ));
+    }
+
+    #[test]
+    fn test_extract_without_message_timestamp() {
+        let extractor = RelativeTimeExtractor::new(RELATIVE_REGEX).unwrap();
+        let now = Utc.ymd(2021, 3, 4).and_hms(1, 0, 0);
+        let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM))
+            .with_clock(Arc::new(FixedClock::new(now)));
+        let actual: Vec<DateTime<Utc>> = extractor.extract("in 2 hours", &ctx);
+        assert_eq!(actual, vec![now + Duration::hours(2)]);
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = RelativeTimeExtractor::new(RELATIVE_REGEX).unwrap();
+                let msg_timestamp = Utc.ymd(2021, 3, 4).and_hms(22, 15, 0);
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(22, 15, TimeKind::Military))
+                    .with_message_timestamp(msg_timestamp);
+                let actual: Vec<DateTime<Utc>> = extractor.extract($input_text, &ctx);
+                let expected: Vec<DateTime<Utc>> = $expected.into_iter().map(|offset: Duration| msg_timestamp + offset).collect();
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_no_match {
+            text: "I'll be in the channel later",
+            expected: vec![],
+        },
+        test_extract_hours {
+            text: "Back in 2 hours",
+            expected: vec![Duration::hours(2)],
+        },
+        test_extract_minutes {
+            text: "raid starts in 90 minutes",
+            expected: vec![Duration::minutes(90)],
+        },
+        test_extract_abbreviated_minutes {
+            text: "in 45 mins",
+            expected: vec![Duration::minutes(45)],
+        },
+        test_extract_article {
+            text: "in an hour",
+            expected: vec![Duration::hours(1)],
+        },
+        test_extract_half_hour {
+            text: "in half an hour",
+            expected: vec![Duration::minutes(30)],
+        },
+        test_extract_fractional_hours {
+            text: "in 1.5 hrs",
+            expected: vec![Duration::minutes(90)],
+        },
+        test_extract_compact_components {
+            text: "in 1h30m",
+            expected: vec![Duration::minutes(90)],
+        },
+        test_extract_spelled_components {
+            text: "in 2 days, 3 hours and 15 minutes",
+            expected: vec![Duration::days(2) + Duration::hours(3) + Duration::minutes(15)],
+        },
+        test_extract_crosses_midnight {
+            text: "in 3 hours",
+            expected: vec![Duration::hours(3)],
+        },
+        test_extract_multi_match {
+            text: "in 10 minutes or in 1 hour",
+            expected: vec![Duration::minutes(10), Duration::hours(1)],
+        },
+        test_extract_ignores_other_units {
+            text: "in 5 seconds or in 3 months",
+            expected: vec![],
+        },
+    }
+}
@@ -1,32 +1,128 @@
-use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
 
-use crate::{extractor::Extractor, time_converter::model::TimeComponents};
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::{ExtractedTime, LocalInstant, TimeComponents, TimeExpression},
+};
 
-use super::TimeExtractorContext;
+use super::{
+    CurrentTimeExtractor, DiscordTimestampExtractor, DynamicTimeExtractor, FixedTimeExtractor,
+    PhraseTimeExtractor, RelativeTimeExtractor, TimeExtractorContext,
+};
 
-impl<C, E> Extractor<C, NaiveTime> for E
-where
-    E: Extractor<C, TimeComponents>,
-{
-    fn extract(&self, text: &str, ctx: &C) -> Vec<NaiveTime> {
-        self.extract(text, ctx)
-            .into_iter()
-            .map(NaiveTime::from)
-            .collect()
+// The conversions from each stage of a time to the next are implemented for the extractors of
+// each stage, rather than for any extractor, so that they don't overlap with the combinators
+// that can extract any type.
+macro_rules! impl_time_conversions {
+    (TimeComponents => $($extractor:ty),*) => {
+        $(
+        impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, TimeExpression> for $extractor {
+            fn extract_matches(
+                &self,
+                text: &str,
+                ctx: &TimeExtractorContext<Tz>,
+            ) -> Vec<Extraction<TimeExpression>> {
+                let times: Vec<Extraction<TimeComponents>> = self.extract_matches(text, ctx);
+                times
+                    .into_iter()
+                    .map(|extraction| extraction.map(TimeExpression::from))
+                    .collect()
+            }
+        }
+        )*
+        impl_time_conversions!(TimeExpression => $($extractor),*);
+    };
+    (TimeExpression => $($extractor:ty),*) => {
+        $(
+        impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, DateTime<Utc>> for $extractor {
+            fn extract_matches(
+                &self,
+                text: &str,
+                ctx: &TimeExtractorContext<Tz>,
+            ) -> Vec<Extraction<DateTime<Utc>>> {
+                let date = local_date(ctx);
+                let expressions: Vec<Extraction<TimeExpression>> = self.extract_matches(text, ctx);
+                expressions
+                    .into_iter()
+                    .filter_map(|extraction| {
+                        extraction.and_then(|expression| to_utc(expression, date, ctx))
+                    })
+                    .collect()
+            }
+        }
+
+        // Extracted times keep both interpretations of a time that's ambiguous or skipped
+        // because of daylight saving time, so they can be pointed out instead of guessed.
+        impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, ExtractedTime> for $extractor {
+            fn extract_matches(
+                &self,
+                text: &str,
+                ctx: &TimeExtractorContext<Tz>,
+            ) -> Vec<Extraction<ExtractedTime>> {
+                let date = local_date(ctx);
+                let expressions: Vec<Extraction<TimeExpression>> = self.extract_matches(text, ctx);
+                expressions
+                    .into_iter()
+                    .map(|extraction| {
+                        extraction.map(|expression| ExtractedTime::Instant(resolve(expression, date, ctx)))
+                    })
+                    .collect()
+            }
+        }
+        )*
+    };
+    (DateTime => $($extractor:ty),*) => {
+        $(
+        impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, ExtractedTime> for $extractor {
+            fn extract_matches(
+                &self,
+                text: &str,
+                ctx: &TimeExtractorContext<Tz>,
+            ) -> Vec<Extraction<ExtractedTime>> {
+                let instants: Vec<Extraction<DateTime<Utc>>> = self.extract_matches(text, ctx);
+                instants
+                    .into_iter()
+                    .map(|extraction| extraction.map(ExtractedTime::from))
+                    .collect()
+            }
+        }
+        )*
+    };
+}
+
+impl_time_conversions!(TimeComponents => CurrentTimeExtractor, FixedTimeExtractor, PhraseTimeExtractor);
+impl_time_conversions!(TimeExpression => DynamicTimeExtractor);
+impl_time_conversions!(DateTime => DiscordTimestampExtractor, RelativeTimeExtractor);
+
+/// The local date that times extracted using the context fall on.
+pub(super) fn local_date<Tz: TimeZone>(ctx: &TimeExtractorContext<Tz>) -> NaiveDate {
+    ctx.date().unwrap_or_else(|| ctx.today())
+}
+
+/// Interprets the expression on the given date, in the zone it was written in or
+/// otherwise in the context's local zone.
+pub(super) fn resolve<Tz: TimeZone>(
+    expression: TimeExpression,
+    date: NaiveDate,
+    ctx: &TimeExtractorContext<Tz>,
+) -> LocalInstant {
+    let local_time = date.and_time(NaiveTime::from(expression.time()));
+    match expression.zone() {
+        Some(zone) => LocalInstant::from_local(&zone, &local_time),
+        None => LocalInstant::from_local(ctx.local_tz(), &local_time),
     }
 }
 
-impl<Tz, E> Extractor<TimeExtractorContext<Tz>, DateTime<Utc>> for E
-where
-    E: Extractor<TimeExtractorContext<Tz>, NaiveTime>,
-    Tz: TimeZone,
-{
-    fn extract(&self, text: &str, ctx: &TimeExtractorContext<Tz>) -> Vec<DateTime<Utc>> {
-        let date_in_local_tz = Utc::now().date().with_timezone(ctx.local_tz());
-        self.extract(text, ctx)
-            .into_iter()
-            .filter_map(|local_time| date_in_local_tz.and_time(local_time))
-            .map(|local_date_time| local_date_time.with_timezone(&Utc))
-            .collect()
+/// Interprets the expression as a single instant, where a time that happens twice is the
+/// earlier of the two and a time that's skipped doesn't exist.
+pub(super) fn to_utc<Tz: TimeZone>(
+    expression: TimeExpression,
+    date: NaiveDate,
+    ctx: &TimeExtractorContext<Tz>,
+) -> Option<DateTime<Utc>> {
+    match resolve(expression, date, ctx) {
+        LocalInstant::Single(instant) => Some(instant),
+        LocalInstant::Repeated { earlier, .. } => Some(earlier),
+        LocalInstant::Skipped { .. } => None,
     }
 }
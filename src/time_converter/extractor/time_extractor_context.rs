@@ -1,16 +1,56 @@
-use chrono::TimeZone;
+use std::sync::Arc;
 
-use crate::time_converter::model::TimeComponents;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+use crate::time_converter::{
+    clock::{Clock, SystemClock},
+    model::TimeComponents,
+};
 
 pub struct TimeExtractorContext<Tz: TimeZone> {
     local_tz: Tz,
     msg_time: TimeComponents,
+    date: Option<NaiveDate>,
+    msg_timestamp: Option<DateTime<Utc>>,
+    clock: Arc<dyn Clock>,
 }
 
 #[allow(dead_code)]
 impl<Tz: TimeZone> TimeExtractorContext<Tz> {
     pub fn new(local_tz: Tz, msg_time: TimeComponents) -> Self {
-        TimeExtractorContext { local_tz, msg_time }
+        TimeExtractorContext {
+            local_tz,
+            msg_time,
+            date: None,
+            msg_timestamp: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Creates a context for a message sent at the timestamp, by an author in the local zone.
+    pub fn for_message(local_tz: Tz, msg_timestamp: DateTime<Utc>) -> Self {
+        let msg_time = msg_timestamp.with_timezone(&local_tz).time();
+        Self::new(local_tz, TimeComponents::from(msg_time)).with_message_timestamp(msg_timestamp)
+    }
+
+    /// Sets the local date that times extracted using this context fall on. Without it,
+    /// times fall on the local date of the message.
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Sets the instant the message was sent, which relative times ("in 2 hours") are
+    /// measured from and dates ("tomorrow") are anchored to.
+    pub fn with_message_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.msg_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the clock used in place of the message's timestamp when there isn't one.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
     }
 
     pub fn local_tz(&self) -> &Tz {
@@ -20,4 +60,79 @@ impl<Tz: TimeZone> TimeExtractorContext<Tz> {
     pub fn message_time(&self) -> TimeComponents {
         self.msg_time
     }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.date
+    }
+
+    pub fn message_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.msg_timestamp
+    }
+
+    /// The instant the message was sent, or otherwise the current time.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.msg_timestamp.unwrap_or_else(|| self.clock.now())
+    }
+
+    /// The local date the message was sent on, or otherwise the current local date.
+    pub fn today(&self) -> NaiveDate {
+        self.now()
+            .with_timezone(&self.local_tz)
+            .date()
+            .naive_local()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono_tz::{America::New_York, Asia::Tokyo};
+
+    use crate::time_converter::{clock::FixedClock, model::TimeKind};
+
+    use super::*;
+
+    fn clock() -> Arc<dyn Clock> {
+        Arc::new(FixedClock::new(Utc.ymd(2021, 3, 14).and_hms(12, 0, 0)))
+    }
+
+    #[test]
+    fn test_for_message() {
+        let timestamp = Utc.ymd(2021, 3, 4).and_hms(20, 30, 0);
+        let ctx = TimeExtractorContext::for_message(Tokyo, timestamp);
+
+        assert_eq!(
+            ctx.message_time(),
+            TimeComponents::of(5, 30, TimeKind::Military)
+        );
+        assert_eq!(ctx.message_timestamp(), Some(timestamp));
+    }
+
+    #[test]
+    fn test_now_without_message_timestamp() {
+        let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM))
+            .with_clock(clock());
+
+        assert_eq!(ctx.now(), Utc.ymd(2021, 3, 14).and_hms(12, 0, 0));
+    }
+
+    #[test]
+    fn test_now_prefers_message_timestamp() {
+        let timestamp = Utc.ymd(2021, 3, 4).and_hms(20, 30, 0);
+        let ctx = TimeExtractorContext::for_message(Utc, timestamp).with_clock(clock());
+
+        assert_eq!(ctx.now(), timestamp);
+    }
+
+    #[test]
+    fn test_today_in_local_zone() {
+        // Sent in the evening of March 4th in UTC, which is already March 5th in Tokyo and
+        // still March 4th in New York.
+        let timestamp = Utc.ymd(2021, 3, 4).and_hms(20, 30, 0);
+
+        let ctx = TimeExtractorContext::for_message(Tokyo, timestamp).with_clock(clock());
+        assert_eq!(ctx.today(), NaiveDate::from_ymd(2021, 3, 5));
+
+        let ctx = TimeExtractorContext::for_message(New_York, timestamp).with_clock(clock());
+        assert_eq!(ctx.today(), NaiveDate::from_ymd(2021, 3, 4));
+    }
 }
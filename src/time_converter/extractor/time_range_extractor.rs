@@ -0,0 +1,327 @@
+use std::{collections::HashSet, str::FromStr};
+
+use chrono::{Duration, TimeZone};
+use fancy_regex::{Captures, Regex};
+use itertools::Itertools;
+
+use crate::{
+    extractor::{Extraction, Extractor},
+    time_converter::model::{ExtractedTime, TimeComponents, TimeExpression, TimeKind, TimeRange},
+};
+
+use super::{
+    extractor_error::{check_allowed_groups, check_required_groups, ExtractorResult},
+    time_extractor::{local_date, resolve},
+    time_zone_aliases::{lookup_zone, AmbiguousZonePolicy},
+    TimeExtractorContext,
+};
+
+#[derive(Debug)]
+pub struct TimeRangeExtractor {
+    regex: Regex,
+    ambiguous_zone_policy: AmbiguousZonePolicy,
+}
+
+const START_HOURS_CAPTURE_NAME: &str = "start_hours";
+const START_MINUTES_CAPTURE_NAME: &str = "start_minutes";
+const START_TIME_KIND_CAPTURE_NAME: &str = "start_time_kind";
+const END_HOURS_CAPTURE_NAME: &str = "end_hours";
+const END_MINUTES_CAPTURE_NAME: &str = "end_minutes";
+const END_TIME_KIND_CAPTURE_NAME: &str = "end_time_kind";
+const ZONE_CAPTURE_NAME: &str = "zone";
+
+const ALLOWED_CAPTURE_GROUPS: [&str; 7] = [
+    START_HOURS_CAPTURE_NAME,
+    START_MINUTES_CAPTURE_NAME,
+    START_TIME_KIND_CAPTURE_NAME,
+    END_HOURS_CAPTURE_NAME,
+    END_MINUTES_CAPTURE_NAME,
+    END_TIME_KIND_CAPTURE_NAME,
+    ZONE_CAPTURE_NAME,
+];
+
+const REQUIRED_CAPTURE_GROUPS: [&str; 2] = [START_HOURS_CAPTURE_NAME, END_HOURS_CAPTURE_NAME];
+
+#[allow(dead_code)]
+impl TimeRangeExtractor {
+    pub fn new(regex: &str) -> ExtractorResult<Self> {
+        let compiled_regex = Regex::new(regex)?;
+        Self::validate_regex(&compiled_regex)?;
+
+        Ok(Self {
+            regex: compiled_regex,
+            ambiguous_zone_policy: AmbiguousZonePolicy::PreferFirst,
+        })
+    }
+
+    pub fn ambiguous_zone_policy(mut self, policy: AmbiguousZonePolicy) -> Self {
+        self.ambiguous_zone_policy = policy;
+        self
+    }
+
+    fn validate_regex(regex: &Regex) -> ExtractorResult<()> {
+        let names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        check_required_groups(&names, &REQUIRED_CAPTURE_GROUPS)?;
+        check_allowed_groups(&names, &ALLOWED_CAPTURE_GROUPS)
+    }
+
+    fn process_captures(&self, captures: &Captures) -> Option<(TimeRange, Option<chrono_tz::Tz>)> {
+        let start_hour = extract_capture::<u32>(captures, START_HOURS_CAPTURE_NAME)?;
+        let start_minute =
+            extract_capture::<u32>(captures, START_MINUTES_CAPTURE_NAME).unwrap_or(0);
+        let start_kind = extract_time_kind(captures, START_TIME_KIND_CAPTURE_NAME)?;
+
+        let end_hour = extract_capture::<u32>(captures, END_HOURS_CAPTURE_NAME)?;
+        let end_minute = extract_capture::<u32>(captures, END_MINUTES_CAPTURE_NAME).unwrap_or(0);
+        let end_kind =
+            extract_time_kind(captures, END_TIME_KIND_CAPTURE_NAME)?.unwrap_or(TimeKind::Military);
+        let end = TimeComponents::new(end_hour, end_minute, end_kind).ok()?;
+
+        let range = TimeRange::new(start_hour, start_minute, start_kind, end).ok()?;
+
+        let zone = match extract_capture::<String>(captures, ZONE_CAPTURE_NAME) {
+            Some(zone_name) => lookup_zone(&zone_name).resolve(self.ambiguous_zone_policy)?,
+            None => None,
+        };
+
+        Some((range, zone))
+    }
+}
+
+impl<Tz: TimeZone> Extractor<TimeExtractorContext<Tz>, ExtractedTime> for TimeRangeExtractor {
+    fn extract_matches(
+        &self,
+        text: &str,
+        ctx: &TimeExtractorContext<Tz>,
+    ) -> Vec<Extraction<ExtractedTime>> {
+        let start_date = local_date(ctx);
+
+        self.regex
+            .captures_iter(text)
+            .filter_map(|result| result.ok())
+            .filter_map(|captures| {
+                let found = captures.get(0)?;
+                let (range, zone) = self.process_captures(&captures)?;
+
+                let end_date = if range.crosses_midnight() {
+                    start_date + Duration::days(1)
+                } else {
+                    start_date
+                };
+
+                let start = resolve(TimeExpression::new(range.start(), zone), start_date, ctx);
+                let end = resolve(TimeExpression::new(range.end(), zone), end_date, ctx);
+                Some(Extraction::new(
+                    found.range(),
+                    found.as_str(),
+                    ExtractedTime::Range { start, end },
+                ))
+            })
+            .unique_by(|extraction| *extraction.value())
+            .collect()
+    }
+}
+
+fn extract_capture<T: FromStr>(captures: &Captures, name: &str) -> Option<T> {
+    captures
+        .name(name)
+        .map(|capture| capture.as_str())
+        .map(|string| string.parse::<T>())
+        .and_then(|result| result.ok())
+}
+
+/// Returns `None` if the time kind is invalid, otherwise the time kind if one was written.
+fn extract_time_kind(captures: &Captures, name: &str) -> Option<Option<TimeKind>> {
+    match extract_capture::<String>(captures, name) {
+        Some(time_kind) => match time_kind.to_uppercase().as_str() {
+            "AM" => Some(Some(TimeKind::AM)),
+            "PM" => Some(Some(TimeKind::PM)),
+            _ => None,
+        },
+        None => Some(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, NaiveDate, Utc};
+    use chrono_tz::America;
+
+    use crate::time_converter::{
+        extractor::{ExtractorError, TIME_ZONE_SUFFIX_PATTERN},
+        model::LocalInstant,
+    };
+
+    use super::*;
+
+    const AM_PM_RANGE_REGEX: &str = r"(?i:(?<![\w:.\-])(?:from\s+)?(?P<start_hours>1[012]|0?[1-9])(?::(?P<start_minutes>[0-5]\d))?\s*(?P<start_time_kind>[ap]m)?\s*(?:-|–|—|~|to|until|till|til|through|thru)\s*(?P<end_hours>1[012]|0?[1-9])(?::(?P<end_minutes>[0-5]\d))?\s*(?P<end_time_kind>[ap]m)(?!\w))";
+    const MILITARY_RANGE_REGEX: &str = r"(?i:(?<![\w:.\-])(?:from\s+)?(?P<start_hours>[01]\d|2[0-3]):(?P<start_minutes>[0-5]\d)\s*(?:-|–|—|~|to|until|till|til|through|thru)\s*(?P<end_hours>[01]\d|2[0-3]):(?P<end_minutes>[0-5]\d)(?![\w:]|[.,]\d))";
+
+    #[test]
+    fn test_missing_capture_groups_1() {
+        let error = TimeRangeExtractor::new(r"(?P<start_hours>\d+)").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::MissingCaptureGroups { missing } if missing == vec!["end_hours"]
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_capture_groups_1() {
+        let error =
+            TimeRangeExtractor::new(r"(?P<start_hours>\d+)-(?P<end_hours>\d+)(?P<seconds>\d+)")
+                .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ExtractorError::UnexpectedCaptureGroups { unexpected, .. } if unexpected == vec!["seconds"]
+        ));
+    }
+
+    fn instant(hour: u32, minute: u32, day_offset: i64) -> DateTime<Utc> {
+        let date = NaiveDate::from_ymd(2021, 3, 4) + Duration::days(day_offset);
+        DateTime::from_utc(date.and_hms(hour, minute, 0), Utc)
+    }
+
+    #[test]
+    fn test_extract_matches_spans() {
+        let extractor = TimeRangeExtractor::new(AM_PM_RANGE_REGEX).unwrap();
+        let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM))
+            .with_date(NaiveDate::from_ymd(2021, 3, 4));
+        let actual: Vec<Extraction<ExtractedTime>> =
+            extractor.extract_matches("I'm free from 5 to 7pm", &ctx);
+
+        assert_eq!(
+            actual,
+            vec![Extraction::new(
+                9..22,
+                "from 5 to 7pm",
+                ExtractedTime::Range {
+                    start: instant(17, 0, 0).into(),
+                    end: instant(19, 0, 0).into()
+                },
+            )]
+        );
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{regex: $input_regex:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = TimeRangeExtractor::new(&$input_regex).unwrap();
+                let ctx = TimeExtractorContext::new(Utc, TimeComponents::of(1, 0, TimeKind::AM))
+                    .with_date(NaiveDate::from_ymd(2021, 3, 4));
+                let actual: Vec<ExtractedTime> = extractor.extract($input_text, &ctx);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_inherited_time_kind {
+            regex: AM_PM_RANGE_REGEX,
+            text: "I'm free 5-7pm",
+            expected: vec![ExtractedTime::Range { start: instant(17, 0, 0).into(), end: instant(19, 0, 0).into() }],
+        },
+        test_extract_explicit_time_kinds {
+            regex: AM_PM_RANGE_REGEX,
+            text: "5am-6pm",
+            expected: vec![ExtractedTime::Range { start: instant(5, 0, 0).into(), end: instant(18, 0, 0).into() }],
+        },
+        test_extract_words {
+            regex: AM_PM_RANGE_REGEX,
+            text: "from 3pm until 5:30pm",
+            expected: vec![ExtractedTime::Range { start: instant(15, 0, 0).into(), end: instant(17, 30, 0).into() }],
+        },
+        test_extract_across_noon {
+            regex: AM_PM_RANGE_REGEX,
+            text: "11 to 1pm",
+            expected: vec![ExtractedTime::Range { start: instant(11, 0, 0).into(), end: instant(13, 0, 0).into() }],
+        },
+        test_extract_across_midnight {
+            regex: AM_PM_RANGE_REGEX,
+            text: "10pm - 2am",
+            expected: vec![ExtractedTime::Range { start: instant(22, 0, 0).into(), end: instant(2, 0, 1).into() }],
+        },
+        test_extract_military {
+            regex: MILITARY_RANGE_REGEX,
+            text: "17:00-19:30",
+            expected: vec![ExtractedTime::Range { start: instant(17, 0, 0).into(), end: instant(19, 30, 0).into() }],
+        },
+        test_extract_military_across_midnight {
+            regex: MILITARY_RANGE_REGEX,
+            text: "from 22:00 to 01:00",
+            expected: vec![ExtractedTime::Range { start: instant(22, 0, 0).into(), end: instant(1, 0, 1).into() }],
+        },
+        test_extract_zone {
+            regex: format!("{}{}", AM_PM_RANGE_REGEX, *TIME_ZONE_SUFFIX_PATTERN),
+            text: "5-7pm EST",
+            expected: vec![ExtractedTime::Range {
+                start: America::New_York.ymd(2021, 3, 4).and_hms(17, 0, 0).with_timezone(&Utc).into(),
+                end: America::New_York.ymd(2021, 3, 4).and_hms(19, 0, 0).with_timezone(&Utc).into(),
+            }],
+        },
+        test_extract_ignores_single_times {
+            regex: AM_PM_RANGE_REGEX,
+            text: "5pm and 7pm",
+            expected: vec![],
+        },
+        test_extract_ignores_dates {
+            regex: AM_PM_RANGE_REGEX,
+            text: "2021-12-03 5pm",
+            expected: vec![],
+        },
+        test_extract_ignores_invalid_start {
+            regex: AM_PM_RANGE_REGEX,
+            text: "17-7pm",
+            expected: vec![],
+        },
+    }
+
+    macro_rules! test_extract_pinned_data {
+        ($($name:ident{date: $input_date:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = TimeRangeExtractor::new(AM_PM_RANGE_REGEX).unwrap();
+                let ctx = TimeExtractorContext::new(America::New_York, TimeComponents::of(9, 0, TimeKind::Military))
+                    .with_date($input_date);
+                let actual: Vec<ExtractedTime> = extractor.extract($input_text, &ctx);
+                let expected: Vec<ExtractedTime> = $expected;
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    // Daylight saving time starts in New York at 2am on March 14th, 2021, and ends at 2am on
+    // November 7th, 2021.
+    test_extract_pinned_data! {
+        test_extract_pinned_skipped {
+            date: NaiveDate::from_ymd(2021, 3, 14),
+            text: "from 2:30 to 4am",
+            expected: vec![ExtractedTime::Range {
+                start: LocalInstant::Skipped {
+                    earlier: Utc.ymd(2021, 3, 14).and_hms(6, 30, 0),
+                    later: Utc.ymd(2021, 3, 14).and_hms(7, 30, 0),
+                },
+                end: Utc.ymd(2021, 3, 14).and_hms(8, 0, 0).into(),
+            }],
+        },
+        test_extract_pinned_repeated {
+            date: NaiveDate::from_ymd(2021, 11, 7),
+            text: "from 12 to 1:30am",
+            expected: vec![ExtractedTime::Range {
+                start: Utc.ymd(2021, 11, 7).and_hms(4, 0, 0).into(),
+                end: LocalInstant::Repeated {
+                    earlier: Utc.ymd(2021, 11, 7).and_hms(5, 30, 0),
+                    later: Utc.ymd(2021, 11, 7).and_hms(6, 30, 0),
+                },
+            }],
+        },
+    }
+}
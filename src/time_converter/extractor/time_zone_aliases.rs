@@ -0,0 +1,270 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono_tz::{America, Asia, Australia, Europe, Pacific, Tz};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Describes how a time zone name that refers to more than one zone, such as "IST"
+/// (India, Ireland or Israel), should be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(dead_code)]
+pub enum AmbiguousZonePolicy {
+    /// Use the most commonly meant zone for the name.
+    PreferFirst,
+    /// Ignore the written zone and interpret the time in the author's local zone.
+    AuthorZone,
+    /// Don't convert the time at all.
+    Skip,
+}
+
+impl FromStr for AmbiguousZonePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "prefer_first" => Ok(AmbiguousZonePolicy::PreferFirst),
+            "author_zone" => Ok(AmbiguousZonePolicy::AuthorZone),
+            "skip" => Ok(AmbiguousZonePolicy::Skip),
+            other => Err(format!(
+                "Unknown ambiguous zone policy [{}], expected one of prefer_first, author_zone or skip",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneLookup {
+    Unique(Tz),
+    /// Candidate zones ordered from most to least commonly meant.
+    Ambiguous(&'static [Tz]),
+    Unknown,
+}
+
+impl ZoneLookup {
+    /// Returns `None` if a time written in this zone shouldn't be converted at all,
+    /// otherwise the zone to interpret it in, if it differs from the author's local zone.
+    pub fn resolve(self, policy: AmbiguousZonePolicy) -> Option<Option<Tz>> {
+        match self {
+            ZoneLookup::Unique(zone) => Some(Some(zone)),
+            ZoneLookup::Unknown => Some(None),
+            ZoneLookup::Ambiguous(zones) => match policy {
+                AmbiguousZonePolicy::PreferFirst => Some(zones.first().copied()),
+                AmbiguousZonePolicy::AuthorZone => Some(None),
+                AmbiguousZonePolicy::Skip => None,
+            },
+        }
+    }
+}
+
+// Abbreviations resolve to the regional zone rather than a fixed offset, since people
+// writing "EST" in the summer almost always mean US Eastern time.
+static ALIASES: Lazy<HashMap<&'static str, &'static [Tz]>> = Lazy::new(|| {
+    let aliases: Vec<(&[&str], &'static [Tz])> = vec![
+        (&["utc", "gmt", "zulu"], &[Tz::UTC]),
+        (
+            &["est", "edt", "et", "eastern", "new york", "nyc", "ny"],
+            &[America::New_York],
+        ),
+        (&["cst"], &[America::Chicago, Asia::Shanghai]),
+        (
+            &["cdt", "ct", "central", "chicago", "texas"],
+            &[America::Chicago],
+        ),
+        (
+            &["mst", "mdt", "mt", "mountain", "denver"],
+            &[America::Denver],
+        ),
+        (&["arizona", "phoenix"], &[America::Phoenix]),
+        (
+            &[
+                "pst",
+                "pdt",
+                "pt",
+                "pacific",
+                "la",
+                "los angeles",
+                "california",
+                "seattle",
+            ],
+            &[America::Los_Angeles],
+        ),
+        (&["akst", "akdt", "alaska"], &[America::Anchorage]),
+        (&["hst", "hawaii", "honolulu"], &[Pacific::Honolulu]),
+        (&["ast"], &[America::Halifax, Asia::Riyadh]),
+        (&["adt", "atlantic", "halifax"], &[America::Halifax]),
+        (&["toronto"], &[America::Toronto]),
+        (&["vancouver"], &[America::Vancouver]),
+        (&["brt", "brazil", "sao paulo"], &[America::Sao_Paulo]),
+        (&["bst"], &[Europe::London, Asia::Dhaka]),
+        (
+            &["uk", "britain", "england", "scotland", "wales", "london"],
+            &[Europe::London],
+        ),
+        (&["ireland", "dublin"], &[Europe::Dublin]),
+        (&["wet", "portugal", "lisbon"], &[Europe::Lisbon]),
+        (&["cet", "cest", "germany", "berlin"], &[Europe::Berlin]),
+        (
+            &["netherlands", "holland", "nl", "amsterdam"],
+            &[Europe::Amsterdam],
+        ),
+        (&["belgium", "brussels"], &[Europe::Brussels]),
+        (&["france", "paris"], &[Europe::Paris]),
+        (&["spain", "madrid"], &[Europe::Madrid]),
+        (&["italy", "rome"], &[Europe::Rome]),
+        (&["switzerland", "zurich"], &[Europe::Zurich]),
+        (&["austria", "vienna"], &[Europe::Vienna]),
+        (&["sweden", "stockholm"], &[Europe::Stockholm]),
+        (&["norway", "oslo"], &[Europe::Oslo]),
+        (&["denmark", "copenhagen"], &[Europe::Copenhagen]),
+        (&["poland", "warsaw"], &[Europe::Warsaw]),
+        (&["eet", "eest", "greece", "athens"], &[Europe::Athens]),
+        (&["finland", "helsinki"], &[Europe::Helsinki]),
+        (&["msk", "moscow"], &[Europe::Moscow]),
+        (&["ist"], &[Asia::Kolkata, Europe::Dublin, Asia::Jerusalem]),
+        (&["india"], &[Asia::Kolkata]),
+        (&["israel"], &[Asia::Jerusalem]),
+        (&["china", "beijing", "shanghai"], &[Asia::Shanghai]),
+        (&["hkt", "hong kong"], &[Asia::Hong_Kong]),
+        (&["sgt", "singapore"], &[Asia::Singapore]),
+        (&["jst", "japan", "tokyo"], &[Asia::Tokyo]),
+        (&["kst", "korea", "seoul"], &[Asia::Seoul]),
+        (
+            &["aest", "aedt", "sydney", "melbourne"],
+            &[Australia::Sydney],
+        ),
+        (&["acst", "acdt", "adelaide"], &[Australia::Adelaide]),
+        (&["awst", "perth"], &[Australia::Perth]),
+        (
+            &["nzst", "nzdt", "new zealand", "auckland"],
+            &[Pacific::Auckland],
+        ),
+    ];
+
+    aliases
+        .into_iter()
+        .flat_map(|(names, zones)| names.iter().map(move |name| (*name, zones)))
+        .collect()
+});
+
+// Short aliases that are also ordinary words, such as "et" in French or "la" in Spanish, which
+// only refer to a zone when they're written in upper case ("3pm ET").
+const UPPER_CASE_ALIASES: [&str; 7] = ["et", "ct", "mt", "pt", "la", "ny", "nl"];
+
+static UTC_OFFSET_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:utc|gmt)\s*(?P<sign>[+-])\s*(?P<hours>\d{1,2})$").unwrap());
+
+/// A regex fragment that matches any time zone name understood by [`lookup_zone`]. It
+/// contains no capture groups, so it can be wrapped in one by the caller.
+pub static TIME_ZONE_PATTERN: Lazy<String> = Lazy::new(|| {
+    let aliases = ALIASES
+        .keys()
+        .filter(|name| !UPPER_CASE_ALIASES.contains(name))
+        .sorted_by_key(|name| std::cmp::Reverse(name.len()))
+        .map(|name| regex::escape(name).replace(' ', r"\s+"))
+        .join("|");
+    let upper_case_aliases = UPPER_CASE_ALIASES
+        .iter()
+        .map(|name| name.to_uppercase())
+        .join("|");
+
+    format!(
+        r"(?:(?i:(?:utc|gmt)\s*[+-]\s*\d{{1,2}}|[a-z]+(?:/[a-z_]+)+|{})|(?-i:{}))",
+        aliases, upper_case_aliases
+    )
+});
+
+/// An optional regex fragment to append to a time pattern so that a zone written after the
+/// time ("3pm EST", "15:00 in UK time") is captured in the `zone` capture group.
+pub static TIME_ZONE_SUFFIX_PATTERN: Lazy<String> = Lazy::new(|| {
+    format!(
+        r"(?:\s*(?:in\s+)?(?P<zone>{})(?:\s+time)?(?![\w/]))?",
+        *TIME_ZONE_PATTERN
+    )
+});
+
+/// Resolves an abbreviation ("CET"), IANA name ("Europe/London"), UTC offset ("UTC+2")
+/// or city/country alias ("UK") to the zone or zones it refers to.
+pub fn lookup_zone(name: &str) -> ZoneLookup {
+    let normalized = name.split_whitespace().join(" ").to_lowercase();
+
+    if let Some(zones) = ALIASES.get(normalized.as_str()) {
+        return match zones {
+            [zone] => ZoneLookup::Unique(*zone),
+            _ => ZoneLookup::Ambiguous(zones),
+        };
+    }
+
+    if let Some(captures) = UTC_OFFSET_REGEX.captures(&normalized) {
+        // The sign of the IANA "Etc/GMT" zones is inverted, so "UTC+2" is "Etc/GMT-2".
+        let inverted_sign = if &captures["sign"] == "+" { "-" } else { "+" };
+        let offset = format!("Etc/GMT{}{}", inverted_sign, &captures["hours"]);
+
+        return match offset.parse::<Tz>() {
+            Ok(zone) => ZoneLookup::Unique(zone),
+            Err(_) => ZoneLookup::Unknown,
+        };
+    }
+
+    match name.parse::<Tz>() {
+        Ok(zone) => ZoneLookup::Unique(zone),
+        Err(_) => ZoneLookup::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! test_lookup_data {
+        ($($name:ident($input:expr, $expected:expr),)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = lookup_zone($input);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_lookup_data! {
+        lookup_abbreviation(r"EST", ZoneLookup::Unique(America::New_York)),
+        lookup_daylight_abbreviation(r"cest", ZoneLookup::Unique(Europe::Berlin)),
+        lookup_country(r"UK", ZoneLookup::Unique(Europe::London)),
+        lookup_multi_word_city(r"New   York", ZoneLookup::Unique(America::New_York)),
+        lookup_iana_name(r"Europe/Amsterdam", ZoneLookup::Unique(Europe::Amsterdam)),
+        lookup_positive_offset(r"UTC+2", ZoneLookup::Unique(chrono_tz::Etc::GMTMinus2)),
+        lookup_negative_offset(r"GMT - 5", ZoneLookup::Unique(chrono_tz::Etc::GMTPlus5)),
+        lookup_ambiguous_ist(r"IST", ZoneLookup::Ambiguous(&[Asia::Kolkata, Europe::Dublin, Asia::Jerusalem])),
+        lookup_ambiguous_cst(r"CST", ZoneLookup::Ambiguous(&[America::Chicago, Asia::Shanghai])),
+        lookup_unknown(r"Mars/Olympus", ZoneLookup::Unknown),
+        lookup_unknown_offset(r"UTC+99", ZoneLookup::Unknown),
+    }
+
+    #[test]
+    fn pattern_matches_every_alias() {
+        let regex = Regex::new(&format!("^{}$", *TIME_ZONE_PATTERN)).unwrap();
+
+        for alias in ALIASES.keys() {
+            let alias = if UPPER_CASE_ALIASES.contains(alias) {
+                alias.to_uppercase()
+            } else {
+                String::from(*alias)
+            };
+            assert!(regex.is_match(&alias), "{} didn't match", alias);
+        }
+    }
+
+    #[test]
+    fn pattern_ignores_lower_case_words() {
+        let regex = Regex::new(&format!("^{}$", *TIME_ZONE_PATTERN)).unwrap();
+
+        for alias in UPPER_CASE_ALIASES.iter() {
+            assert!(!regex.is_match(alias), "{} matched", alias);
+            let capitalized = format!("{}{}", alias[..1].to_uppercase(), &alias[1..]);
+            assert!(!regex.is_match(&capitalized), "{} matched", capitalized);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod user_extractor_context;
+mod user_reference_extractor;
+
+pub use user_extractor_context::UserExtractorContext;
+pub use user_reference_extractor::MentionedUserExtractor;
@@ -0,0 +1,17 @@
+use serenity::model::id::UserId;
+
+pub struct UserExtractorContext {
+    mentioned_users: Vec<UserId>,
+}
+
+#[allow(dead_code)]
+impl UserExtractorContext {
+    pub fn new(mentioned_users: Vec<UserId>) -> Self {
+        Self { mentioned_users }
+    }
+
+    /// The users mentioned in the message, which are the only users that can be extracted.
+    pub fn mentioned_users(&self) -> &[UserId] {
+        &self.mentioned_users
+    }
+}
@@ -1,49 +1,154 @@
-use std::{cell::RefCell, collections::HashMap};
+use std::collections::HashSet;
 
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serenity::model::id::UserId;
 
-use crate::extractor::Extractor;
+use crate::extractor::{Extraction, Extractor};
 
-use super::UserExtractorContext;
+use super::{
+    super::{ExtractorError, ExtractorResult},
+    UserExtractorContext,
+};
 
+// Mentions of users with a nickname contain an exclamation mark.
+const MENTION_PATTERN: &str = r"<@!?(?P<user>\d+)>";
+
+static MENTION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(MENTION_PATTERN).unwrap());
+
+/// Extracts the users that a question is asked about, such as "what time is it for @alice?".
+///
+/// The regex is written as a template, where the placeholder stands in for the mention of
+/// the user, so it's compiled with a placeholder matching any mention.
 #[derive(Debug)]
 pub struct MentionedUserExtractor {
-    regex_template: String,
-    mention_placeholder: String,
-    regex_cache: RwLock<HashMap<UserId, Regex>>,
+    // Only matches when the mention is at the end of the text, so that each mention in a list
+    // of mentions can be matched on its own.
+    regex: Regex,
 }
 
+#[allow(dead_code)]
 impl MentionedUserExtractor {
-    pub fn new(regex_template: &str, mention_placeholder: &str) -> Self {
-        Self {
-            regex_template: regex_template.to_string(),
-            mention_placeholder: mention_placeholder.to_string(),
-            regex_cache: HashMap::new(),
+    pub fn new(regex_template: &str, mention_placeholder: &str) -> ExtractorResult<Self> {
+        if !regex_template.contains(mention_placeholder) {
+            return Err(ExtractorError::MissingPlaceholder {
+                placeholder: mention_placeholder.to_string(),
+            });
         }
-    }
 
-    fn user_regex(&mut self, user_id: UserId) -> &Regex {
-        let regex_template = self.regex_template.as_str();
-        let mention_placeholder = self.mention_placeholder.as_str();
-
-        self.regex_cache.entry(user_id).or_insert_with(|| {
-            let mention_text = format!("<!{}>", user_id);
-            let regex_text = regex_template.replace(&mention_placeholder, &mention_text);
-            Regex::new(&regex_text).expect("Failed to compile regex")
+        let regex_text = regex_template.replace(mention_placeholder, MENTION_PATTERN);
+        Ok(Self {
+            regex: Regex::new(&format!(r"(?:{})\z", regex_text))?,
         })
     }
 }
 
 impl Extractor<UserExtractorContext, UserId> for MentionedUserExtractor {
-    fn extract(&self, text: &str, ctx: &UserExtractorContext) -> Vec<UserId> {
-        let regexes: Vec<&Regex> = ctx
-            .mentioned_users()
-            .iter()
-            .map(|user| user.id)
-            .map(|user_id| self.user_regex(user_id))
-            .collect();
-
-        todo!()
+    fn extract_matches(&self, text: &str, ctx: &UserExtractorContext) -> Vec<Extraction<UserId>> {
+        let mut found_users = HashSet::new();
+        MENTION_REGEX
+            .find_iter(text)
+            .filter_map(|mention| {
+                // Matching the text up to each mention finds the mentions in a list, which all
+                // start at the same place.
+                let captures = self.regex.captures(&text[..mention.end()])?;
+                let user_id = UserId(captures.name("user")?.as_str().parse().ok()?);
+                if !ctx.mentioned_users().contains(&user_id) || !found_users.insert(user_id) {
+                    return None;
+                }
+
+                let found = captures.get(0)?;
+                Some(Extraction::new(found.range(), found.as_str(), user_id))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PLACEHOLDER: &str = "{mention}";
+    const TIME_FOR_TEMPLATE: &str =
+        r"(?i:\btime\s+(?:is\s+it\s+)?(?:for|at)\s+(?:<@!?\d+>\s*(?:,|and|&)?\s*)*{mention})";
+
+    #[test]
+    fn test_missing_placeholder() {
+        let error = MentionedUserExtractor::new(r"what time is it for", PLACEHOLDER).unwrap_err();
+
+        assert!(matches!(error, ExtractorError::MissingPlaceholder { .. }));
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let error = MentionedUserExtractor::new(r"(what time is it for {mention}", PLACEHOLDER)
+            .unwrap_err();
+
+        assert!(matches!(error, ExtractorError::InvalidRegex(_)));
+    }
+
+    #[test]
+    fn test_extract_matches_span() {
+        let extractor = MentionedUserExtractor::new(TIME_FOR_TEMPLATE, PLACEHOLDER).unwrap();
+        let ctx = UserExtractorContext::new(vec![UserId(42)]);
+        let actual = extractor.extract_matches("So what time is it for <@42>?", &ctx);
+
+        assert_eq!(
+            actual,
+            vec![Extraction::new(8..28, "time is it for <@42>", UserId(42))]
+        );
+    }
+
+    macro_rules! test_extract_data {
+        ($($name:ident{text: $input_text:expr, mentioned: $mentioned:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let extractor = MentionedUserExtractor::new(TIME_FOR_TEMPLATE, PLACEHOLDER).unwrap();
+                let ctx = UserExtractorContext::new($mentioned.into_iter().map(UserId).collect());
+                let actual: Vec<UserId> = extractor.extract($input_text, &ctx);
+                let expected: Vec<UserId> = $expected.into_iter().map(UserId).collect();
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_extract_data! {
+        test_extract_no_mentions {
+            text: "what time is it for you?",
+            mentioned: Vec::<u64>::new(),
+            expected: Vec::<u64>::new(),
+        },
+        test_extract_single_mention {
+            text: "what time is it for <@42>?",
+            mentioned: vec![42],
+            expected: vec![42],
+        },
+        test_extract_nickname_mention {
+            text: "What's the time at <@!42>",
+            mentioned: vec![42],
+            expected: vec![42],
+        },
+        test_extract_multiple_mentions {
+            text: "what time is it for <@7>, <@!42> and <@13>?",
+            mentioned: vec![42, 13, 7],
+            expected: vec![7, 42, 13],
+        },
+        test_extract_ignores_unrelated_mentions {
+            text: "<@42> what time is it for <@7>?",
+            mentioned: vec![42, 7],
+            expected: vec![7],
+        },
+        test_extract_repeated_mention {
+            text: "what time is it for <@42>? and time for <@42>?",
+            mentioned: vec![42],
+            expected: vec![42],
+        },
+        test_extract_ignores_unmentioned_users {
+            text: "what time is it for <@42>?",
+            mentioned: vec![7],
+            expected: Vec::<u64>::new(),
+        },
     }
 }
@@ -0,0 +1,134 @@
+use crate::time_converter::{
+    extractor::{
+        DynamicTimeExtractor, FixedTimeExtractor, InferredTimeExtractor, TIME_ZONE_SUFFIX_PATTERN,
+    },
+    model::{DayPart, TimeComponents, TimeKind},
+};
+
+use super::LocalePack;
+
+const DAY_PART_WORDS: [(&str, DayPart); 5] = [
+    ("ochtend", DayPart::Morning),
+    ("morgen", DayPart::Morning),
+    ("middag", DayPart::Afternoon),
+    ("avond", DayPart::Evening),
+    ("nacht", DayPart::Night),
+];
+
+/// Times such as "15.30 uur", "3 uur 's middags" or "vanavond om 8". Two digit hours with
+/// "uur" are on the 24-hour clock, but "om 3 uur" is usually said about the afternoon, so
+/// single digit hours need a part of the day or inference. Hours on their own are also
+/// durations ("het duurt 10 uur"), so they need minutes or a word such as "om" in front.
+pub(super) fn pack() -> LocalePack {
+    LocalePack {
+        fixed_times: vec![
+            FixedTimeExtractor::new(
+                r"(?i:\bmiddernacht\b)",
+                TimeComponents::new(0, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+            FixedTimeExtractor::new(
+                r"(?i:\bmiddaguur\b)",
+                TimeComponents::new(12, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+        ],
+        times: vec![
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:(?<![\w:.])(?P<hours>[01]\d|2[0-3])[:.](?P<minutes>[0-5]\d)\s*uur\b{})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:\b(?:om|rond|tegen|vanaf)\s+(?P<hours>[01]\d|2[0-3])(?:[:.](?P<minutes>[0-5]\d))?\s*uur\b{})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(
+                r"(?i:(?<![\w:.])(?P<hours>1[0-2]|0?[1-9])(?:[:.](?P<minutes>[0-5]\d))?(?:\s*uur)?\s+(?P<day_part>['’]s\s*(?:ochtends|morgens|middags|avonds|nachts)|in\s+de\s+(?:ochtend|morgen|middag|avond|nacht))\b)",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+            DynamicTimeExtractor::new(
+                r"(?i:\b(?P<day_part>vanochtend|vanmorgen|vanmiddag|vanavond|vannacht)\s+(?:om\s+|rond\s+)?(?P<hours>1[0-2]|0?[1-9])(?:[:.](?P<minutes>[0-5]\d))?(?:\s*uur)?(?![\w:]))",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+        ],
+        inferred_times: vec![InferredTimeExtractor::new(
+            r"(?i:\b(?:om|rond|tegen)\s+(?P<hours>1[0-2]|0?[1-9])(?:[:.](?P<minutes>[0-5]\d))?(?:\s*uur)?(?![\w:]|[.,]\d))",
+        )
+        .expect("Expected a valid built-in regex.")
+        .day_part_hints(
+            r"(?i:(?P<day_part>['’]s\s*(?:ochtends|morgens|middags|avonds|nachts)|\b(?:vanochtend|vanmorgen|vanmiddag|vanavond|vannacht)\b|\bin\s+de\s+(?:ochtend|morgen|middag|avond|nacht)\b))",
+        )
+        .expect("Expected a valid built-in regex.")
+        .day_part_words(&DAY_PART_WORDS)],
+        prefilter: vec![r"(?i:middernacht|middaguur)"],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::Locale;
+
+    use super::super::corpus::{at, inferred};
+
+    test_corpus! {
+        Locale::Dutch,
+        test_corpus_hours {
+            text: "We beginnen om 15 uur",
+            expected: vec![("om 15 uur", at(15, 0))],
+        },
+        test_corpus_hours_and_minutes {
+            text: "De trein vertrekt 09.45 uur of 17:30 uur",
+            expected: vec![("09.45 uur", at(9, 45)), ("17:30 uur", at(17, 30))],
+        },
+        test_corpus_afternoon {
+            text: "om 3 uur 's middags",
+            expected: vec![("3 uur 's middags", at(15, 0))],
+        },
+        test_corpus_evening_curly_apostrophe {
+            text: "8:30 ’s avonds",
+            expected: vec![("8:30 ’s avonds", at(20, 30))],
+        },
+        test_corpus_night {
+            text: "om 2 uur 's nachts",
+            expected: vec![("2 uur 's nachts", at(2, 0))],
+        },
+        test_corpus_in_the_morning {
+            text: "10 uur in de ochtend",
+            expected: vec![("10 uur in de ochtend", at(10, 0))],
+        },
+        test_corpus_day_part_first {
+            text: "Vanavond om 8 uur?",
+            expected: vec![("Vanavond om 8 uur", at(20, 0))],
+        },
+        test_corpus_midnight {
+            text: "Om middernacht gaat de server uit",
+            expected: vec![("middernacht", at(0, 0))],
+        },
+        test_corpus_noon {
+            text: "rond het middaguur",
+            expected: vec![("middaguur", at(12, 0))],
+        },
+        test_corpus_inferred {
+            text: "zullen we om 3 uur afspreken?",
+            expected: vec![("om 3 uur", inferred(15, 0))],
+        },
+        test_corpus_inferred_with_hint {
+            text: "vanmorgen, rond 10:30",
+            expected: vec![("rond 10:30", inferred(10, 30))],
+        },
+        test_corpus_ignores_durations {
+            text: "Het duurt 3 uur en kost 12.50",
+            expected: vec![],
+        },
+        test_corpus_ignores_two_digit_durations {
+            text: "het duurt 10 uur",
+            expected: vec![],
+        },
+    }
+}
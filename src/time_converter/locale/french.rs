@@ -0,0 +1,137 @@
+use crate::time_converter::{
+    extractor::{DynamicTimeExtractor, FixedTimeExtractor, TIME_ZONE_SUFFIX_PATTERN},
+    model::{DayPart, TimeComponents, TimeKind},
+};
+
+use super::LocalePack;
+
+const DAY_PART_WORDS: [(&str, DayPart); 4] = [
+    ("matin", DayPart::Morning),
+    ("après-midi", DayPart::Afternoon),
+    ("soir", DayPart::Evening),
+    ("nuit", DayPart::Night),
+];
+
+/// Times such as "15h30", "à 9 heures" or "8h du soir". Times written with "h" are on the
+/// 24-hour clock, which is how times are usually written in French, so there's nothing left
+/// to infer AM/PM for. Hours on their own are also durations ("ça dure 3 heures"), so they
+/// need two digits or a word such as "à" in front of them.
+pub(super) fn pack() -> LocalePack {
+    LocalePack {
+        fixed_times: vec![
+            FixedTimeExtractor::new(
+                r"(?i:\bminuit\b)",
+                TimeComponents::new(0, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+            // "Midi" on its own is also part of "après-midi".
+            FixedTimeExtractor::new(
+                r"(?i:\b(?:à|vers|avant|dès)\s+midi\b)",
+                TimeComponents::new(12, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+        ],
+        times: vec![
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:(?<![\w:.])(?P<hours>[01]?\d|2[0-3])\s?h(?:eures?)?\s?(?P<minutes>[0-5]\d)(?![\w:]|[.,]\d){})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:(?<![\w:.])(?P<hours>[01]\d|2[0-3])\s?h(?:\s?(?P<minutes>[0-5]\d))?(?![\w:]|[.,]\d){})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:\b(?:à|vers|dès)\s+(?P<hours>[01]?\d|2[0-3])\s?h(?:eures?)?(?:\s?(?P<minutes>[0-5]\d))?(?![\w:]|[.,]\d){})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(
+                r"(?i:(?<![\w:.])(?P<hours>1[0-2]|0?[1-9])\s?h(?:eures?)?(?:\s?(?P<minutes>[0-5]\d))?\s+(?P<day_part>du\s+matin|de\s+l['’]après-midi|du\s+soir|de\s+la\s+nuit)(?![\w-]))",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+            DynamicTimeExtractor::new(
+                r"(?i:\b(?P<day_part>ce\s+(?:matin|soir)|cet\s+après-midi|cette\s+nuit)\s+(?:à\s+|vers\s+)?(?P<hours>1[0-2]|0?[1-9])\s?h(?:eures?)?(?:\s?(?P<minutes>[0-5]\d))?(?![\w:]))",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+        ],
+        inferred_times: vec![],
+        prefilter: vec![r"(?i:minuit|midi)"],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::Locale;
+
+    use super::super::corpus::at;
+
+    test_corpus! {
+        Locale::French,
+        test_corpus_hours {
+            text: "Rendez-vous à 15h",
+            expected: vec![("à 15h", at(15, 0))],
+        },
+        test_corpus_hours_and_minutes {
+            text: "à 15h30 ou 18 h 45",
+            expected: vec![("à 15h30", at(15, 30)), ("18 h 45", at(18, 45))],
+        },
+        test_corpus_heures {
+            text: "à 9 heures",
+            expected: vec![("à 9 heures", at(9, 0))],
+        },
+        test_corpus_evening {
+            text: "à 8h du soir",
+            expected: vec![("8h du soir", at(20, 0))],
+        },
+        test_corpus_afternoon {
+            text: "3h de l'après-midi",
+            expected: vec![("3h de l'après-midi", at(15, 0))],
+        },
+        test_corpus_night {
+            text: "à 1h du matin",
+            expected: vec![("1h du matin", at(1, 0))],
+        },
+        test_corpus_day_part_first {
+            text: "Ce soir à 9h30 ?",
+            expected: vec![("Ce soir à 9h30", at(21, 30))],
+        },
+        test_corpus_midnight {
+            text: "jusqu'à minuit",
+            expected: vec![("minuit", at(0, 0))],
+        },
+        test_corpus_noon {
+            text: "On mange à midi",
+            expected: vec![("à midi", at(12, 0))],
+        },
+        test_corpus_ignores_afternoon {
+            text: "Cet après-midi, peut-être",
+            expected: vec![],
+        },
+        test_corpus_ignores_et {
+            text: "On joue à 15h et 16h",
+            expected: vec![("à 15h", at(15, 0)), ("16h", at(16, 0))],
+        },
+        test_corpus_two_digit_hours {
+            text: "Le live commence 21h",
+            expected: vec![("21h", at(21, 0))],
+        },
+        test_corpus_ignores_duration_in_heures {
+            text: "ça dure 3 heures",
+            expected: vec![],
+        },
+        test_corpus_ignores_duration_in_h {
+            text: "j'ai joué 2h hier",
+            expected: vec![],
+        },
+        test_corpus_ignores_words {
+            text: "15 habitants et 3 heureux",
+            expected: vec![],
+        },
+    }
+}
@@ -0,0 +1,131 @@
+use crate::time_converter::{
+    extractor::{
+        DynamicTimeExtractor, FixedTimeExtractor, InferredTimeExtractor, TIME_ZONE_SUFFIX_PATTERN,
+    },
+    model::{DayPart, TimeComponents, TimeKind},
+};
+
+use super::LocalePack;
+
+// "Vormittag" and "Nachmittag" contain "Mittag", so they're looked for first.
+const DAY_PART_WORDS: [(&str, DayPart); 7] = [
+    ("vormittag", DayPart::Morning),
+    ("nachmittag", DayPart::Afternoon),
+    ("mittag", DayPart::Afternoon),
+    ("morgen", DayPart::Morning),
+    ("früh", DayPart::Morning),
+    ("abend", DayPart::Evening),
+    ("nacht", DayPart::Night),
+];
+
+/// Times such as "15 Uhr", "3 Uhr nachmittags" or "heute Abend um 8". Times written with
+/// "Uhr" are on the 24-hour clock, which is how times are usually written in German.
+pub(super) fn pack() -> LocalePack {
+    LocalePack {
+        fixed_times: vec![
+            FixedTimeExtractor::new(
+                r"(?i:\bmitternacht\b)",
+                TimeComponents::new(0, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+            // "Mittag" on its own is as often lunch as it is noon.
+            FixedTimeExtractor::new(
+                r"(?i:\b(?:um|gegen|ab|bis)\s+mittag\b)",
+                TimeComponents::new(12, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+        ],
+        times: vec![
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:(?<![\w:.])(?P<hours>[01]?\d|2[0-3])(?:[:.](?P<minutes>[0-5]\d))?\s*uhr\b{})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(
+                r"(?i:(?<![\w:.])(?P<hours>1[0-2]|0?[1-9])(?:[:.](?P<minutes>[0-5]\d))?(?:\s*uhr)?\s+(?P<day_part>morgens|früh|vormittags|mittags|nachmittags|abends|nachts|am\s+(?:morgen|vormittag|nachmittag|abend)|in\s+der\s+(?:früh|nacht))\b)",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+            DynamicTimeExtractor::new(
+                r"(?i:\b(?P<day_part>heute\s+(?:morgen|vormittag|nachmittag|abend|nacht)|morgen\s+früh)\s+(?:um\s+|gegen\s+)?(?P<hours>1[0-2]|0?[1-9])(?:[:.](?P<minutes>[0-5]\d))?(?:\s*uhr)?(?![\w:]))",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+        ],
+        inferred_times: vec![InferredTimeExtractor::new(
+            r"(?i:\b(?:um|gegen|ab)\s+(?P<hours>1[0-2]|0?[1-9])(?:[:.](?P<minutes>[0-5]\d))?(?![\w:]|[.,]\d|\s*uhr\b))",
+        )
+        .expect("Expected a valid built-in regex.")
+        .day_part_hints(
+            r"(?i:\b(?P<day_part>heute\s+(?:morgen|vormittag|nachmittag|abend|nacht)|morgen\s+früh|morgens|vormittags|nachmittags|abends|nachts)\b)",
+        )
+        .expect("Expected a valid built-in regex.")
+        .day_part_words(&DAY_PART_WORDS)],
+        prefilter: vec![r"(?i:mitternacht|mittag)"],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::Locale;
+
+    use super::super::corpus::{at, inferred};
+
+    test_corpus! {
+        Locale::German,
+        test_corpus_hours {
+            text: "Treffen um 15 Uhr",
+            expected: vec![("15 Uhr", at(15, 0))],
+        },
+        test_corpus_single_digit_hours {
+            text: "Frühstück um 8 Uhr",
+            expected: vec![("8 Uhr", at(8, 0))],
+        },
+        test_corpus_hours_and_minutes {
+            text: "ab 18.30 Uhr oder 20:15 Uhr",
+            expected: vec![("18.30 Uhr", at(18, 30)), ("20:15 Uhr", at(20, 15))],
+        },
+        test_corpus_afternoon {
+            text: "um 3 Uhr nachmittags",
+            expected: vec![("3 Uhr nachmittags", at(15, 0))],
+        },
+        test_corpus_morning_before_noon {
+            text: "11 Uhr vormittags",
+            expected: vec![("11 Uhr vormittags", at(11, 0))],
+        },
+        test_corpus_noon_day_part {
+            text: "12 Uhr mittags",
+            expected: vec![("12 Uhr mittags", at(12, 0))],
+        },
+        test_corpus_evening {
+            text: "9 Uhr am Abend",
+            expected: vec![("9 Uhr am Abend", at(21, 0))],
+        },
+        test_corpus_day_part_first {
+            text: "Heute Abend um 8?",
+            expected: vec![("Heute Abend um 8", at(20, 0))],
+        },
+        test_corpus_midnight {
+            text: "Der Server startet um Mitternacht neu",
+            expected: vec![("Mitternacht", at(0, 0))],
+        },
+        test_corpus_noon {
+            text: "Wir sehen uns gegen Mittag",
+            expected: vec![("gegen Mittag", at(12, 0))],
+        },
+        test_corpus_ignores_lunch {
+            text: "Was gibt es zu Mittag?",
+            expected: vec![],
+        },
+        test_corpus_inferred {
+            text: "Sollen wir um 3 spielen?",
+            expected: vec![("um 3", inferred(15, 0))],
+        },
+        test_corpus_inferred_with_hint {
+            text: "morgens, gegen 7",
+            expected: vec![("gegen 7", inferred(7, 0))],
+        },
+    }
+}
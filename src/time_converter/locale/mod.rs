@@ -0,0 +1,187 @@
+use crate::config::Locale;
+
+use super::extractor::{DynamicTimeExtractor, FixedTimeExtractor, InferredTimeExtractor};
+
+// The language tests share a corpus format, so the macro is defined before the modules.
+#[cfg(test)]
+macro_rules! test_corpus {
+    ($locale:expr, $($name:ident{text: $input_text:expr, expected: $expected:expr,},)*) => {
+        $(
+        #[test]
+        fn $name(){
+            let actual = $crate::time_converter::locale::corpus::extract($locale, $input_text);
+            let expected: Vec<(&str, $crate::time_converter::model::ExtractedTime)> = $expected;
+            let expected: Vec<(String, $crate::time_converter::model::ExtractedTime)> = expected
+                .into_iter()
+                .map(|(text, time)| (String::from(text), time))
+                .collect();
+            assert_eq!(actual, expected);
+        }
+        )*
+    };
+}
+
+mod dutch;
+mod french;
+mod german;
+mod spanish;
+
+/// The extractors for the ways times are written in a language, which are used on top of
+/// the English ones.
+#[derive(Debug, Default)]
+pub struct LocalePack {
+    /// Words for fixed times, such as noon and midnight.
+    pub fixed_times: Vec<FixedTimeExtractor>,
+    /// Times on the 24-hour clock, or on the 12-hour clock with a part of the day.
+    pub times: Vec<DynamicTimeExtractor>,
+    /// Bare hours that AM/PM has to be inferred for, only used when inference is enabled.
+    pub inferred_times: Vec<InferredTimeExtractor>,
+    /// Patterns for the prefilter that match the times written without a digit, which the
+    /// built-in prefilter doesn't know about.
+    pub prefilter: Vec<&'static str>,
+}
+
+impl LocalePack {
+    pub fn new(locale: Locale) -> Self {
+        match locale {
+            // The English extractors are always used, so there's nothing to add.
+            Locale::English => LocalePack::default(),
+            Locale::Dutch => dutch::pack(),
+            Locale::German => german::pack(),
+            Locale::French => french::pack(),
+            Locale::Spanish => spanish::pack(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod corpus {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, NaiveDate, Utc};
+    use chrono_tz::Tz;
+    use once_cell::sync::Lazy;
+
+    use crate::{
+        extractor::{Extraction, Extractor},
+        time_converter::{
+            definitions::ExtractorDefinitions,
+            extractor::{OverlapResolver, Prefilter, TimeExtractorContext},
+            model::{ExtractedTime, TimeComponents, TimeKind},
+        },
+    };
+
+    use super::*;
+
+    type TimeResolver = OverlapResolver<TimeExtractorContext<Tz>, ExtractedTime>;
+
+    // Loading the built-in definitions is slow, so each locale's extractors are only combined
+    // with them once, without and with the prefilter.
+    static BUILT_IN_RESOLVERS: Lazy<HashMap<Locale, (TimeResolver, TimeResolver)>> =
+        Lazy::new(|| {
+            Locale::ALL
+                .iter()
+                .map(|&locale| {
+                    let resolvers = (resolver(locale, true, false), resolver(locale, true, true));
+                    (locale, resolvers)
+                })
+                .collect()
+        });
+
+    /// Extracts times from a text with only the extractors and prefilter of a locale, as
+    /// written at 9:00 UTC on 2021-03-04 by someone in UTC.
+    ///
+    /// The prefilter only makes searching faster, so the text is also checked to give the
+    /// same times without it, both for the locale alone and on top of the built-in extractors.
+    pub(super) fn extract(locale: Locale, text: &str) -> Vec<(String, ExtractedTime)> {
+        let (built_in_resolver, prefiltered_built_in_resolver) = &BUILT_IN_RESOLVERS[&locale];
+        assert_same_times(built_in_resolver, prefiltered_built_in_resolver, text);
+
+        let actual = assert_same_times(
+            &resolver(locale, false, false),
+            &resolver(locale, false, true),
+            text,
+        );
+        actual
+            .into_iter()
+            .map(|extraction| (String::from(extraction.text()), *extraction.value()))
+            .collect()
+    }
+
+    fn assert_same_times(
+        resolver: &TimeResolver,
+        prefiltered_resolver: &TimeResolver,
+        text: &str,
+    ) -> Vec<Extraction<ExtractedTime>> {
+        let ctx = TimeExtractorContext::new(Tz::UTC, TimeComponents::of(9, 0, TimeKind::Military))
+            .with_date(NaiveDate::from_ymd(2021, 3, 4))
+            .with_message_timestamp(instant(9, 0));
+        let expected = resolver.extract_matches(text, &ctx);
+        let actual = prefiltered_resolver.extract_matches(text, &ctx);
+        assert_eq!(actual, expected, "{}", text);
+        actual
+    }
+
+    /// Combines the extractors of a locale, on top of the built-in ones like the message
+    /// handler does when `built_in` is set, and the prefilter for all of them when
+    /// `prefiltered` is set.
+    fn resolver(locale: Locale, built_in: bool, prefiltered: bool) -> TimeResolver {
+        let pack = LocalePack::new(locale);
+        let mut resolver = OverlapResolver::new();
+        let mut patterns: Vec<String> = pack.prefilter.iter().map(|p| p.to_string()).collect();
+        if built_in {
+            let definitions = ExtractorDefinitions::load(None).unwrap();
+            for (priority, extractor) in definitions.time_extractors(true) {
+                resolver = resolver.with(priority, extractor);
+            }
+            patterns.extend(definitions.prefilter().unwrap().iter().cloned());
+        } else {
+            patterns.push(String::from(r"\d"));
+        }
+        if prefiltered {
+            resolver = resolver.prefilter(Prefilter::new(&patterns).unwrap());
+        }
+
+        for extractor in pack.fixed_times {
+            resolver = resolver.with(1, Box::new(extractor));
+        }
+        for extractor in pack.times {
+            resolver = resolver.with(1, Box::new(extractor));
+        }
+        for extractor in pack.inferred_times {
+            resolver = resolver.with(0, Box::new(extractor));
+        }
+        resolver
+    }
+
+    #[test]
+    fn test_prefilter_keeps_corpus_times() {
+        let corpus = include_str!("../../../benches/corpus.txt")
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.replace("\\n", "\n"));
+
+        for message in corpus {
+            for (resolver, prefiltered_resolver) in BUILT_IN_RESOLVERS.values() {
+                assert_same_times(resolver, prefiltered_resolver, &message);
+            }
+        }
+    }
+
+    fn instant(hour: u32, minute: u32) -> DateTime<Utc> {
+        DateTime::from_utc(
+            NaiveDate::from_ymd(2021, 3, 4).and_hms(hour, minute, 0),
+            Utc,
+        )
+    }
+
+    /// A time on the day the corpus messages were written.
+    pub(super) fn at(hour: u32, minute: u32) -> ExtractedTime {
+        ExtractedTime::Instant(instant(hour, minute).into())
+    }
+
+    /// A time on the day the corpus messages were written, with AM/PM inferred.
+    pub(super) fn inferred(hour: u32, minute: u32) -> ExtractedTime {
+        ExtractedTime::Inferred(instant(hour, minute).into())
+    }
+}
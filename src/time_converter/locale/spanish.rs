@@ -0,0 +1,128 @@
+use crate::time_converter::{
+    extractor::{
+        DynamicTimeExtractor, FixedTimeExtractor, InferredTimeExtractor, TIME_ZONE_SUFFIX_PATTERN,
+    },
+    model::{DayPart, TimeComponents, TimeKind},
+};
+
+use super::LocalePack;
+
+// The small hours ("las 3 de la madrugada") are always before noon.
+const DAY_PART_WORDS: [(&str, DayPart); 4] = [
+    ("madrugada", DayPart::Morning),
+    ("mañana", DayPart::Morning),
+    ("tarde", DayPart::Afternoon),
+    ("noche", DayPart::Night),
+];
+
+/// Times such as "a las 15:30", "las 3 de la tarde" or "esta noche a las 9". Times are
+/// usually said on the 12-hour clock ("a las 3"), so only hours that can't be AM/PM are
+/// taken to be on the 24-hour clock.
+pub(super) fn pack() -> LocalePack {
+    LocalePack {
+        fixed_times: vec![
+            FixedTimeExtractor::new(
+                r"(?i:\bmedianoche\b)",
+                TimeComponents::new(0, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+            FixedTimeExtractor::new(
+                r"(?i:\bmediod[ií]a\b)",
+                TimeComponents::new(12, 0, TimeKind::Military)
+                    .expect("Expected valid time components."),
+            )
+            .expect("Expected a valid built-in regex."),
+        ],
+        times: vec![
+            DynamicTimeExtractor::new(&format!(
+                r"(?i:\b(?:a|desde|hasta)\s+las\s+(?P<hours>0\d|1[3-9]|2[0-3])(?::(?P<minutes>[0-5]\d))?(?:\s*(?:h|hs|horas)\b)?(?![\w:]|[.,]\d){})",
+                *TIME_ZONE_SUFFIX_PATTERN
+            ))
+            .expect("Expected a valid built-in regex."),
+            DynamicTimeExtractor::new(
+                r"(?i:(?<![\w:.])(?P<hours>1[0-2]|0?[1-9])(?::(?P<minutes>[0-5]\d))?\s+(?P<day_part>(?:de|por)\s+la\s+(?:mañana|tarde|noche|madrugada))\b)",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+            DynamicTimeExtractor::new(
+                r"(?i:\b(?P<day_part>esta\s+(?:mañana|tarde|noche))\s+(?:a\s+las?\s+|sobre\s+las?\s+|hacia\s+las?\s+)?(?P<hours>1[0-2]|0?[1-9])(?::(?P<minutes>[0-5]\d))?(?![\w:]))",
+            )
+            .expect("Expected a valid built-in regex.")
+            .day_part_words(&DAY_PART_WORDS),
+        ],
+        inferred_times: vec![InferredTimeExtractor::new(
+            r"(?i:\b(?:a|desde|hasta|sobre|hacia)\s+las?\s+(?P<hours>1[0-2]|0?[1-9])(?::(?P<minutes>[0-5]\d))?(?![\w:]|[.,]\d))",
+        )
+        .expect("Expected a valid built-in regex.")
+        .day_part_hints(
+            r"(?i:\b(?P<day_part>esta\s+(?:mañana|tarde|noche)|(?:de|por)\s+la\s+(?:mañana|tarde|noche|madrugada))\b)",
+        )
+        .expect("Expected a valid built-in regex.")
+        .day_part_words(&DAY_PART_WORDS)],
+        prefilter: vec![r"(?i:medianoche|mediod[ií]a)"],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::Locale;
+
+    use super::super::corpus::{at, inferred};
+
+    test_corpus! {
+        Locale::Spanish,
+        test_corpus_hours {
+            text: "Quedamos a las 15 horas",
+            expected: vec![("a las 15 horas", at(15, 0))],
+        },
+        test_corpus_hours_and_minutes {
+            text: "desde las 18:30h",
+            expected: vec![("desde las 18:30h", at(18, 30))],
+        },
+        test_corpus_ignores_la {
+            text: "desde las 18:30h la partida",
+            expected: vec![("desde las 18:30h", at(18, 30))],
+        },
+        test_corpus_afternoon {
+            text: "a las 3 de la tarde",
+            expected: vec![("3 de la tarde", at(15, 0))],
+        },
+        test_corpus_morning {
+            text: "a las 10:30 de la mañana",
+            expected: vec![("10:30 de la mañana", at(10, 30))],
+        },
+        test_corpus_small_hours {
+            text: "a las 5 de la madrugada",
+            expected: vec![("5 de la madrugada", at(5, 0))],
+        },
+        test_corpus_night {
+            text: "las 11 de la noche",
+            expected: vec![("11 de la noche", at(23, 0))],
+        },
+        test_corpus_day_part_first {
+            text: "¿Esta noche a las 9?",
+            expected: vec![("Esta noche a las 9", at(21, 0))],
+        },
+        test_corpus_midnight {
+            text: "a medianoche",
+            expected: vec![("medianoche", at(0, 0))],
+        },
+        test_corpus_noon {
+            text: "al mediodia",
+            expected: vec![("mediodia", at(12, 0))],
+        },
+        test_corpus_inferred {
+            text: "¿Jugamos a las 3?",
+            expected: vec![("a las 3", inferred(15, 0))],
+        },
+        test_corpus_inferred_one {
+            text: "a la 1",
+            expected: vec![("a la 1", inferred(13, 0))],
+        },
+        test_corpus_inferred_tomorrow_is_not_morning {
+            text: "mañana a las 8",
+            expected: vec![("a las 8", inferred(20, 0))],
+        },
+    }
+}
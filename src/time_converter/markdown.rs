@@ -0,0 +1,163 @@
+use std::{collections::HashSet, ops::Range};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::MarkdownRegion;
+
+// Regions are listed in order of precedence, e.g. backticks inside a code block don't start
+// inline code.
+static REGION_REGEXES: Lazy<Vec<(MarkdownRegion, Regex)>> = Lazy::new(|| {
+    [
+        (MarkdownRegion::CodeBlocks, r"(?s)```.*?```"),
+        (MarkdownRegion::InlineCode, r"``(?:[^`]|`[^`])+?``|`[^`]+`"),
+        (MarkdownRegion::Links, r"<?https?://[^\s<>]+>?"),
+        (MarkdownRegion::Spoilers, r"(?s)\|\|.+?\|\|"),
+        (MarkdownRegion::Quotes, r"(?ms)^>>> .*\z|^> .*?$"),
+    ]
+    .iter()
+    .map(|(region, regex)| {
+        (
+            *region,
+            Regex::new(regex).expect("Failed to compile regex."),
+        )
+    })
+    .collect()
+});
+
+/// Blanks out the parts of a message that shouldn't have times converted, such as code
+/// blocks or quotes of other people's messages.
+#[derive(Debug, Clone)]
+pub struct MarkdownMask {
+    regions: HashSet<MarkdownRegion>,
+}
+
+impl MarkdownMask {
+    pub fn new(regions: HashSet<MarkdownRegion>) -> Self {
+        Self { regions }
+    }
+
+    /// Replaces the masked regions of the text with spaces. Every character is replaced
+    /// by as many spaces as it takes bytes, so byte offsets into the masked text are also
+    /// offsets into the original text. Line breaks are kept.
+    pub fn mask(&self, text: &str) -> String {
+        let mut claimed: Vec<(MarkdownRegion, Range<usize>)> = Vec::new();
+
+        // Each kind of region is only looked for outside of the regions found before it.
+        let mut remaining = String::from(text);
+        for (region, regex) in REGION_REGEXES.iter() {
+            let spans: Vec<Range<usize>> = regex
+                .find_iter(&remaining)
+                .map(|found| found.range())
+                .collect();
+            remaining = blank(&remaining, &spans);
+            claimed.extend(spans.into_iter().map(|span| (*region, span)));
+        }
+
+        let masked_spans: Vec<Range<usize>> = claimed
+            .into_iter()
+            .filter(|(region, _)| self.regions.contains(region))
+            .map(|(_, span)| span)
+            .collect();
+
+        blank(text, &masked_spans)
+    }
+}
+
+fn blank(text: &str, spans: &[Range<usize>]) -> String {
+    let mut blanked = String::with_capacity(text.len());
+    for (index, c) in text.char_indices() {
+        if c != '\n' && spans.iter().any(|span| span.contains(&index)) {
+            blanked.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        } else {
+            blanked.push(c);
+        }
+    }
+
+    blanked
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn all_regions() -> HashSet<MarkdownRegion> {
+        MarkdownRegion::ALL.iter().copied().collect()
+    }
+
+    macro_rules! test_mask_data {
+        ($($name:ident{regions: $input_regions:expr, text: $input_text:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = MarkdownMask::new($input_regions).mask($input_text);
+                assert_eq!(actual, $expected);
+                assert_eq!(actual.len(), $input_text.len());
+            }
+            )*
+        };
+    }
+
+    test_mask_data! {
+        test_mask_plain_text {
+            regions: all_regions(),
+            text: "see you at 5pm",
+            expected: "see you at 5pm",
+        },
+        test_mask_code_block {
+            regions: all_regions(),
+            text: "log:\n```\n[5pm] started\n```\nat 6pm",
+            expected: "log:\n   \n             \n   \nat 6pm",
+        },
+        test_mask_inline_code {
+            regions: all_regions(),
+            text: "run `sleep 5pm` at 6pm",
+            expected: "run             at 6pm",
+        },
+        test_mask_double_backtick_inline_code {
+            regions: all_regions(),
+            text: "``a ` 5pm`` 6pm",
+            expected: "            6pm",
+        },
+        test_mask_backticks_in_code_block {
+            regions: all_regions(),
+            text: "```a`b``` `5pm`",
+            expected: "               ",
+        },
+        test_mask_links {
+            regions: all_regions(),
+            text: "https://example.com/5pm and [at 6pm](<https://example.com/7pm>)",
+            expected: "                        and [at 6pm](                         )",
+        },
+        test_mask_spoilers {
+            regions: all_regions(),
+            text: "||dies at 5pm|| at 6pm",
+            expected: "                at 6pm",
+        },
+        test_mask_quote {
+            regions: all_regions(),
+            text: "> meet at 5pm\nno, 6pm",
+            expected: "             \nno, 6pm",
+        },
+        test_mask_multi_line_quote {
+            regions: all_regions(),
+            text: "6pm\n>>> 5pm\n7pm",
+            expected: "6pm\n       \n   ",
+        },
+        test_mask_keeps_byte_offsets {
+            regions: all_regions(),
+            text: "||é 5pm|| 6pm",
+            expected: "           6pm",
+        },
+        test_mask_only_configured_regions {
+            regions: [MarkdownRegion::Spoilers].iter().copied().collect(),
+            text: "`5pm` ||6pm||",
+            expected: "`5pm`        ",
+        },
+        test_mask_unconfigured_region_still_claims_text {
+            regions: [MarkdownRegion::InlineCode].iter().copied().collect(),
+            text: "```a `5pm` b```",
+            expected: "```a `5pm` b```",
+        },
+    }
+}
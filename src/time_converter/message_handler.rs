@@ -1,79 +1,184 @@
 use std::{clone::Clone, collections::HashMap, fmt::Debug, sync::Arc};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 use itertools::Itertools;
 use log::debug;
+use serde_json::Value;
 use serenity::{
     async_trait,
     client::{Context, EventHandler},
-    model::{channel::Message, id::RoleId},
+    model::{
+        channel::Message,
+        event::MessageUpdateEvent,
+        id::{GuildId, MessageId, RoleId, UserId},
+    },
     utils::MessageBuilder,
 };
 
 use chrono_tz::Tz;
 
 use crate::{
-    config::{Config, LocationRole},
-    extractor::Extractor,
+    config::{Config, DayShiftFormat, Locale, LocationRole, ResponseStyle, TimeOutputMode},
+    extractor::{AsyncExtractor, Extraction, Extractor},
     user_roles::UserRoleCache,
 };
 
 use super::{
+    clock::{Clock, SystemClock},
+    definitions::ExtractorDefinitions,
+    embed::{self, TimeEmbed},
     extractor::{
-        CurrentTimeExtractor, DynamicTimeExtractor, FixedTimeExtractor, TimeExtractorContext,
+        MentionedUserExtractor, OverlapResolver, Prefilter, TimeExtractorContext,
+        UserExtractorContext,
     },
-    model::{TimeComponents, TimeKind},
+    locale::LocalePack,
+    markdown::MarkdownMask,
+    model::{DayShift, DiscordTimestamp, ExtractedTime, LocalInstant, TimestampStyle},
+    output_zones::{OutputZoneStore, OutputZones},
+    reply_tracker::ReplyTracker,
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct TimeZoneInfo {
-    name: String,
-    tz: Tz,
+// The extractors are asynchronous, so extractors that look something up, such as a user's
+// roles, can be combined with the synchronous ones.
+type TimeExtractor = Box<dyn AsyncExtractor<TimeExtractorContext<Tz>, ExtractedTime>>;
+type DateExtractor = Box<dyn AsyncExtractor<TimeExtractorContext<Tz>, NaiveDate>>;
+type UserExtractor = Box<dyn AsyncExtractor<UserExtractorContext, UserId>>;
+
+/// A reply to a message, with text and embeds.
+#[derive(Debug, Default)]
+struct Reply {
+    content: Option<String>,
+    embeds: Vec<TimeEmbed>,
 }
 
-impl TimeZoneInfo {
-    fn new(name: &str, tz: Tz) -> Self {
+impl Reply {
+    fn text(content: String) -> Self {
         Self {
-            name: String::from(name),
-            tz,
+            content: Some(content),
+            embeds: Vec::new(),
         }
     }
+
+    fn embeds(embeds: Vec<TimeEmbed>) -> Self {
+        Self {
+            content: None,
+            embeds,
+        }
+    }
+
+    /// Adds the text and embeds of another reply after this reply's.
+    fn append(mut self, other: Reply) -> Self {
+        self.content = match (self.content, other.content) {
+            (Some(content), Some(other_content)) => Some(format!("{}\n{}", content, other_content)),
+            (content, other_content) => content.or(other_content),
+        };
+        self.embeds.extend(other.embeds);
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.content.is_none() && self.embeds.is_empty()
+    }
+
+    // The message builders only set a single embed, so the list of embeds is set directly.
+    fn embeds_json(&self) -> Value {
+        Value::Array(self.embeds.iter().map(TimeEmbed::to_json).collect())
+    }
 }
 
-type TimeExtractor = Box<dyn Extractor<TimeExtractorContext<Tz>, DateTime<Utc>>>;
+// The priorities of times written in a locale's language, which match the built-in
+// definitions of the same kind.
+const TIME_PRIORITY: u32 = 1;
+const INFERRED_PRIORITY: u32 = 0;
 
 #[derive(Debug)]
 pub struct MessageHandler {
-    // TODO : Consider whether _config will ever be used
-    _config: Arc<Config>,
+    config: Arc<Config>,
     user_role_cache: Arc<UserRoleCache>,
-    time_extractors: Vec<TimeExtractor>,
+    time_extractors: HashMap<Locale, TimeExtractor>,
+    date_extractor: DateExtractor,
+    user_extractor: UserExtractor,
     input_timezones: HashMap<RoleId, LocationRole>,
-    output_timezones: Vec<TimeZoneInfo>,
+    output_zones: Arc<OutputZoneStore>,
+    replies: ReplyTracker,
+    clock: Arc<dyn Clock>,
+    output_mode: TimeOutputMode,
+    markdown_mask: MarkdownMask,
     output_time_fmt: String,
+    output_date_time_fmt: String,
 }
 
 impl MessageHandler {
-    pub fn new(config: Arc<Config>, user_role_cache: Arc<UserRoleCache>) -> Self {
-        let time_extractors: Vec<TimeExtractor> = vec![
-            Box::new(FixedTimeExtractor::new(
-                r"(?i:midnight)",
-                TimeComponents::new(0, 0, TimeKind::Military)
-                    .expect("Expected valid time components."),
-            )),
-            Box::new(FixedTimeExtractor::new(
-                r"(?i:noon|midday)",
-                TimeComponents::new(12, 0, TimeKind::Military)
-                    .expect("Expected valid time components."),
-            )),
-            Box::new(CurrentTimeExtractor::new(
-                r"(?i:what\s+time\s+is\s+it\s+now|(?:current\s+time))",
-            )),
-            Box::new(DynamicTimeExtractor::new(
-                r"(?i:(?<!\w)(?P<hours>(?:1[012])|(?:0?[123456789]))\s*(?::\s*(?P<minutes>(?:[12345]\d)|(?:0\d)))?\s*(?P<time_kind>[ap]m)(?!\w))",
-            )),
-        ];
+    fn time_extractor(
+        config: &Config,
+        definitions: &ExtractorDefinitions,
+        locale: Locale,
+    ) -> TimeExtractor {
+        let mut time_extractor = OverlapResolver::new();
+        for (priority, extractor) in definitions.time_extractors(config.infer_time_kind()) {
+            time_extractor = time_extractor.with(priority, extractor);
+        }
+
+        // Times written in the locale's language are converted on top of English ones.
+        let pack = LocalePack::new(locale);
+        if let Some(patterns) = definitions.prefilter() {
+            let patterns = patterns.iter().map(String::as_str).chain(pack.prefilter);
+            let prefilter = Prefilter::new(patterns).expect("Expected a valid prefilter.");
+            time_extractor = time_extractor.prefilter(prefilter);
+        }
+
+        for extractor in pack.fixed_times {
+            time_extractor = time_extractor.with(TIME_PRIORITY, Box::new(extractor));
+        }
+        for extractor in pack.times {
+            time_extractor = time_extractor.with(TIME_PRIORITY, Box::new(extractor));
+        }
+
+        if config.infer_time_kind() {
+            for extractor in pack.inferred_times {
+                time_extractor = time_extractor.with(INFERRED_PRIORITY, Box::new(extractor));
+            }
+        }
+
+        // Typed times are only precise to the minute, so they're considered the same as any
+        // other mention of the same minute ("12am midnight"), and only the first mention is
+        // converted.
+        let time_extractor = time_extractor.dedup_by(|time: &ExtractedTime| {
+            (
+                time.start().timestamp() / 60,
+                time.end().map(|end| end.timestamp() / 60),
+            )
+        });
+        Box::new(time_extractor.into_async())
+    }
+
+    pub fn new(
+        config: Arc<Config>,
+        user_role_cache: Arc<UserRoleCache>,
+        output_zones: Arc<OutputZoneStore>,
+        definitions: &ExtractorDefinitions,
+    ) -> Self {
+        let time_extractors = Locale::ALL
+            .iter()
+            .map(|locale| (*locale, Self::time_extractor(&config, definitions, *locale)))
+            .collect();
+
+        let date_extractor: DateExtractor = Box::new(
+            definitions
+                .date_extractors()
+                .dedup_by(|date: &NaiveDate| *date)
+                .into_async(),
+        );
+
+        let user_extractor: UserExtractor = Box::new(
+            MentionedUserExtractor::new(
+                r"(?i:\b(?:what(?:'s|\s+is)\s+the\s+)?time\s+(?:is\s+it\s+)?(?:for|at|with)\s+(?:<@!?\d+>\s*(?:,|and|&)?\s*)*{mention})",
+                "{mention}",
+            )
+            .expect("Expected a valid built-in regex.")
+            .into_async(),
+        );
 
         let input_timezones = config
             .location_roles()
@@ -82,20 +187,23 @@ impl MessageHandler {
             .map(|location_role| (location_role.role_id(), location_role))
             .collect();
 
-        let output_timezones = vec![
-            TimeZoneInfo::new("Netherlands", chrono_tz::Europe::Amsterdam),
-            TimeZoneInfo::new("UK", chrono_tz::Europe::London),
-            TimeZoneInfo::new("US East", chrono_tz::America::New_York),
-            TimeZoneInfo::new("US West", chrono_tz::America::Los_Angeles),
-        ];
+        let output_mode = config.time_output_mode();
+        let markdown_mask = MarkdownMask::new(config.masked_markdown().clone());
 
         Self {
-            _config: config,
+            config,
             user_role_cache,
             time_extractors,
+            date_extractor,
+            user_extractor,
             input_timezones,
-            output_timezones,
+            output_zones,
+            replies: ReplyTracker::default(),
+            clock: Arc::new(SystemClock),
+            output_mode,
+            markdown_mask,
             output_time_fmt: String::from("%_I:%M %p %Z"),
+            output_date_time_fmt: String::from("%a %b %e, %_I:%M %p %Z"),
         }
     }
 
@@ -115,41 +223,451 @@ impl MessageHandler {
         }
     }
 
-    fn format_time(&self, time: &DateTime<Utc>, tz_info: &TimeZoneInfo) -> String {
-        let zoned_time = time.with_timezone(&tz_info.tz);
-        let formatted_time = zoned_time.format(&self.output_time_fmt);
-        format!("{:<12}: {}", tz_info.name, formatted_time)
+    fn format_time(&self, time: &DateTime<Utc>, tz: &Tz, include_date: bool) -> String {
+        let zoned_time = time.with_timezone(tz);
+        let fmt = if include_date {
+            &self.output_date_time_fmt
+        } else {
+            &self.output_time_fmt
+        };
+        zoned_time.format(fmt).to_string()
+    }
+
+    /// Formats a time in a zone, marking it when it's on a different day than it is for the
+    /// author, e.g. "8:00 AM CET (+1 day)".
+    fn format_shifted_time(
+        &self,
+        time: &DateTime<Utc>,
+        tz: &Tz,
+        local_tz: &Tz,
+        include_date: bool,
+    ) -> String {
+        let formatted_time = self.format_time(time, tz, include_date);
+        let marker = DayShift::between(time, tz, local_tz).and_then(|shift| {
+            match self.config.day_shift_format() {
+                DayShiftFormat::Offset => Some(shift.to_string()),
+                DayShiftFormat::Weekday => Some(shift.weekday().to_string()),
+                DayShiftFormat::Hidden => None,
+            }
+        });
+
+        match marker {
+            Some(marker) => format!("{} ({})", formatted_time, marker),
+            None => formatted_time,
+        }
+    }
+
+    /// Formats a local time in a zone, with both instants it could be when the clocks are
+    /// changed around it.
+    fn format_local_instant(
+        &self,
+        instant: &LocalInstant,
+        tz: &Tz,
+        local_tz: &Tz,
+        include_date: bool,
+    ) -> String {
+        match *instant {
+            LocalInstant::Single(instant) => {
+                self.format_shifted_time(&instant, tz, local_tz, include_date)
+            }
+            LocalInstant::Repeated { earlier, later }
+            | LocalInstant::Skipped { earlier, later } => {
+                format!(
+                    "{} or {}",
+                    self.format_shifted_time(&earlier, tz, local_tz, include_date),
+                    self.format_shifted_time(&later, tz, local_tz, include_date)
+                )
+            }
+        }
+    }
+
+    fn format_extracted_time(
+        &self,
+        time: &ExtractedTime,
+        tz: &Tz,
+        local_tz: &Tz,
+        include_date: bool,
+    ) -> String {
+        match *time {
+            ExtractedTime::Instant(instant) | ExtractedTime::Inferred(instant) => {
+                self.format_local_instant(&instant, tz, local_tz, include_date)
+            }
+            ExtractedTime::Range { start, end } => {
+                // Only repeat the date at the end of the range if it's a different day.
+                let is_same_day = start.earliest().with_timezone(tz).date()
+                    == end.earliest().with_timezone(tz).date();
+                format!(
+                    "{} – {}",
+                    self.format_local_instant(&start, tz, local_tz, include_date),
+                    self.format_local_instant(&end, tz, local_tz, include_date && !is_same_day)
+                )
+            }
+        }
+    }
+
+    fn format_native_instant(instant: &LocalInstant, style: TimestampStyle) -> String {
+        match *instant {
+            LocalInstant::Single(instant) => DiscordTimestamp::new(instant, style).to_string(),
+            LocalInstant::Repeated { earlier, later }
+            | LocalInstant::Skipped { earlier, later } => {
+                format!(
+                    "{} or {}",
+                    DiscordTimestamp::new(earlier, style),
+                    DiscordTimestamp::new(later, style)
+                )
+            }
+        }
+    }
+
+    fn format_native_time(&self, time: &ExtractedTime, include_date: bool) -> String {
+        let style = if include_date {
+            TimestampStyle::LongDateTime
+        } else {
+            TimestampStyle::ShortTime
+        };
+
+        let formatted_time = match *time {
+            ExtractedTime::Instant(instant) | ExtractedTime::Inferred(instant) => {
+                Self::format_native_instant(&instant, style)
+            }
+            ExtractedTime::Range { start, end } => format!(
+                "{} – {}",
+                Self::format_native_instant(&start, style),
+                Self::format_native_instant(&end, TimestampStyle::ShortTime)
+            ),
+        };
+
+        let relative = DiscordTimestamp::new(time.start(), TimestampStyle::Relative);
+        format!("{} ({})", formatted_time, relative)
+    }
+
+    /// Explains why a conversion might not be what the author meant.
+    fn conversion_notes(time: &ExtractedTime) -> Vec<&'static str> {
+        let mut notes = Vec::new();
+        if let ExtractedTime::Inferred(_) = time {
+            notes.push("(AM/PM inferred)");
+        }
+
+        let instants = time.instants();
+        if instants
+            .iter()
+            .any(|instant| matches!(instant, LocalInstant::Repeated { .. }))
+        {
+            notes.push(
+                "(⚠ this time happens twice because the clocks are turned back, \
+                so it's converted both from before and from after they're turned back)",
+            );
+        }
+        if instants
+            .iter()
+            .any(|instant| matches!(instant, LocalInstant::Skipped { .. }))
+        {
+            notes.push(
+                "(⚠ this time doesn't exist because the clocks are turned forward past it, \
+                so it's converted both as if they hadn't been turned forward yet and as if they had)",
+            );
+        }
+        notes
     }
 
-    fn construct_response(&self, times: &[DateTime<Utc>]) -> Option<String> {
+    fn construct_response(
+        &self,
+        times: &[Extraction<ExtractedTime>],
+        output_zones: &OutputZones,
+        guild_id: GuildId,
+        local_tz: Tz,
+        include_date: bool,
+    ) -> Option<Reply> {
         if times.is_empty() {
             return None;
         }
 
+        if self.config.response_style() == ResponseStyle::Embed {
+            let embeds =
+                self.construct_embeds(times, output_zones, guild_id, local_tz, include_date);
+            // Too many conversions for one message's embeds are all listed as text instead, so
+            // none of them are left out.
+            if embed::fit_in_message(&embeds) {
+                return Some(Reply::embeds(embeds));
+            }
+        }
+
+        Some(Reply::text(self.construct_code_blocks(
+            times,
+            output_zones,
+            local_tz,
+            include_date,
+        )))
+    }
+
+    /// Lays out each conversion as an embed titled with the part of the message it was
+    /// converted from, with a field for each zone.
+    fn construct_embeds(
+        &self,
+        times: &[Extraction<ExtractedTime>],
+        output_zones: &OutputZones,
+        guild_id: GuildId,
+        local_tz: Tz,
+        include_date: bool,
+    ) -> Vec<TimeEmbed> {
+        let colour = self.config.embed_colour(guild_id);
+        let footer = format!("Converted from {}", local_tz.name());
+
+        times
+            .iter()
+            .map(|extraction| {
+                let time = extraction.value();
+
+                let mut description = Vec::new();
+                if self.output_mode.includes_native() {
+                    description.push(self.format_native_time(time, include_date));
+                }
+                for note in Self::conversion_notes(time) {
+                    description.push(format!("*{}*", note));
+                }
+
+                let mut embed = TimeEmbed::new(extraction.text().trim(), colour).footer(&footer);
+                if !description.is_empty() {
+                    embed = embed.description(&description.join(" "));
+                }
+
+                if self.output_mode.includes_fixed_zones() {
+                    for output_zone in output_zones.iter() {
+                        let formatted_time = self.format_extracted_time(
+                            time,
+                            &output_zone.zone(),
+                            &local_tz,
+                            include_date,
+                        );
+                        embed = embed.field(output_zone.label(), &formatted_time);
+                    }
+                }
+
+                embed
+            })
+            .collect()
+    }
+
+    /// Lays out each conversion as a line naming the part of the message it was converted
+    /// from, followed by a code block with a line for each zone.
+    fn construct_code_blocks(
+        &self,
+        times: &[Extraction<ExtractedTime>],
+        output_zones: &OutputZones,
+        local_tz: Tz,
+        include_date: bool,
+    ) -> String {
         let mut content = MessageBuilder::new();
-        for time in times {
-            let block = self
-                .output_timezones
+        for extraction in times {
+            let time = extraction.value();
+
+            // Label each conversion with the part of the message it was converted from.
+            content
+                .push("'")
+                .push_safe(extraction.text().trim())
+                .push("' →");
+
+            if self.output_mode.includes_native() {
+                content
+                    .push(" ")
+                    .push(self.format_native_time(time, include_date));
+            }
+
+            for note in Self::conversion_notes(time) {
+                content.push(" ").push_italic(note);
+            }
+
+            content.push_line("");
+
+            if self.output_mode.includes_fixed_zones() {
+                let block = output_zones
+                    .iter()
+                    .map(|output_zone| {
+                        let formatted_time = self.format_extracted_time(
+                            time,
+                            &output_zone.zone(),
+                            &local_tz,
+                            include_date,
+                        );
+                        format!("{:<12}: {}", output_zone.label(), formatted_time)
+                    })
+                    .join("\n");
+
+                content.push_codeblock(block, None).push_line("");
+            }
+        }
+
+        content.build()
+    }
+
+    /// Converts the times mentioned in the message from the author's local time zone.
+    async fn time_response(
+        &self,
+        ctx: &Context,
+        msg: &Message,
+        guild_id: GuildId,
+        content: &str,
+    ) -> Option<Reply> {
+        let roles_results = self
+            .user_role_cache
+            .roles(ctx, msg.author.id, guild_id)
+            .await;
+
+        let roles = match roles_results {
+            Ok(roles) => roles,
+            // There was an error, so skip further processing
+            _ => {
+                // TODO : log this error
+                return None;
+            }
+        };
+
+        let tz = match self.resolve_local_tz(&roles) {
+            Ok(tz) => tz,
+            _ => return None, // TODO : log this error
+        };
+
+        // Times and dates are anchored to when the message was sent, rather than when it's
+        // processed, which can be a lot later after a reconnect.
+        let extractor_ctx = TimeExtractorContext::for_message(tz, msg.timestamp);
+
+        let mentioned_dates = self.date_extractor.extract(content, &extractor_ctx).await;
+
+        // Times can only be anchored to a mentioned date when it's clear which date they
+        // refer to, so messages mentioning several dates fall back to the current date.
+        let mentioned_date = match mentioned_dates.as_slice() {
+            [date] => Some(*date),
+            _ => None,
+        };
+
+        let extractor_ctx = match mentioned_date {
+            Some(date) => extractor_ctx.with_date(date),
+            None => extractor_ctx,
+        };
+
+        // Each part of the message has at most one interpretation, and conversions are
+        // listed in the order they appear in the message.
+        let locale = self.config.locale(guild_id, msg.channel_id);
+        let extracted_times = self.time_extractors[&locale]
+            .extract_matches(content, &extractor_ctx)
+            .await;
+
+        let output_zones = self.output_zones.zones(guild_id).await;
+        self.construct_response(
+            &extracted_times,
+            &output_zones,
+            guild_id,
+            tz,
+            mentioned_date.is_some(),
+        )
+    }
+
+    /// Answers questions about the local time of the users mentioned in the message, such as
+    /// "what time is it for @alice?".
+    async fn user_time_response(
+        &self,
+        ctx: &Context,
+        msg: &Message,
+        guild_id: GuildId,
+        content: &str,
+    ) -> Option<String> {
+        let user_ctx = UserExtractorContext::new(msg.mentions.iter().map(|user| user.id).collect());
+        let user_ids: Vec<UserId> = self.user_extractor.extract(content, &user_ctx).await;
+
+        if user_ids.is_empty() {
+            return None;
+        }
+
+        let mut response = MessageBuilder::new();
+        for user_id in user_ids {
+            let name = msg
+                .mentions
                 .iter()
-                .map(|tz_info| self.format_time(time, tz_info))
-                .join("\n");
+                .find(|user| user.id == user_id)
+                .map(|user| user.name.as_str())
+                .unwrap_or_default();
+
+            let tz = match self.user_role_cache.roles(ctx, user_id, guild_id).await {
+                Ok(roles) => self.resolve_local_tz(&roles).ok(),
+                _ => None,
+            };
 
-            content.push_codeblock(block, None);
+            response.push_bold_safe(name);
+            match tz {
+                // The message may be an edit of one sent long ago, so the answer is the time
+                // now rather than when the message was sent.
+                Some(tz) => response.push_line(format!(
+                    ": {}",
+                    self.format_time(&self.clock.now(), &tz, true)
+                )),
+                None => response.push_line(" doesn't have a location role"),
+            };
         }
 
-        Some(content.build())
+        Some(response.build())
     }
 
-    async fn reply(&self, ctx: &Context, msg: &Message, content: &str) {
+    /// Answers the times and users mentioned in the message, which is empty when it doesn't
+    /// mention any.
+    async fn respond(&self, ctx: &Context, msg: &Message, guild_id: GuildId) -> Reply {
+        // Times in code, quotes, links and spoilers aren't converted. The masked content is
+        // the same length as the original, so spans in it are also spans in the original.
+        let content = self.markdown_mask.mask(&msg.content);
+
+        vec![
+            self.user_time_response(ctx, msg, guild_id, &content)
+                .await
+                .map(Reply::text),
+            self.time_response(ctx, msg, guild_id, &content).await,
+        ]
+        .into_iter()
+        .flatten()
+        .fold(Reply::default(), Reply::append)
+    }
+
+    /// Sends the reply to the message, and remembers it so it can be updated when the message
+    /// is edited.
+    async fn reply(&self, ctx: &Context, msg: &Message, reply: &Reply) {
         // TODO : Do something with the errors
-        let _ = msg
+        let sent = msg
             .channel_id
             .send_message(ctx, |reply_msg| {
-                reply_msg.content(&content);
+                if let Some(content) = &reply.content {
+                    reply_msg.content(content);
+                }
+                if !reply.embeds.is_empty() {
+                    reply_msg.0.insert("embeds", reply.embeds_json());
+                }
                 reply_msg.reference_message(msg);
                 reply_msg
             })
             .await;
+
+        if let Ok(sent) = sent {
+            self.replies.track(msg.id, sent.id).await;
+        }
+    }
+
+    /// Replaces an earlier reply to the message as a whole, so any text or embeds that it no
+    /// longer has are removed.
+    async fn edit_reply(&self, ctx: &Context, msg: &Message, reply_id: MessageId, reply: &Reply) {
+        // TODO : Do something with the errors
+        let _ = msg
+            .channel_id
+            .edit_message(ctx, reply_id, |edit_msg| {
+                edit_msg.content(reply.content.as_deref().unwrap_or_default());
+                edit_msg.0.insert("embeds", reply.embeds_json());
+                edit_msg
+            })
+            .await;
+    }
+
+    /// Deletes an earlier reply to the message, once the message no longer mentions anything
+    /// to answer.
+    async fn retract_reply(&self, ctx: &Context, msg: &Message, reply_id: MessageId) {
+        self.replies.forget(msg.id).await;
+        // TODO : Do something with the errors
+        let _ = msg.channel_id.delete_message(ctx, reply_id).await;
     }
 }
 
@@ -171,39 +689,44 @@ impl EventHandler for MessageHandler {
             _ => return,
         };
 
-        let roles_results = self
-            .user_role_cache
-            .roles(&ctx, msg.author.id, guild_id)
-            .await;
+        let reply = self.respond(&ctx, &msg, guild_id).await;
+        if !reply.is_empty() {
+            self.reply(&ctx, &msg, &reply).await;
+        }
+    }
 
-        let roles = match roles_results {
-            Ok(roles) => roles,
-            // There was an error, so skip further processing
-            _ => {
-                // TODO : log this error
-                return;
-            }
-        };
+    async fn message_update(&self, ctx: Context, event: MessageUpdateEvent) {
+        // Updates that don't change the content, such as a link getting a preview, can't
+        // change the times in the message.
+        if event.content.is_none() {
+            return;
+        }
 
-        let tz = match self.resolve_local_tz(&roles) {
-            Ok(tz) => tz,
+        // The update only has the fields that changed, so the whole message is fetched.
+        let msg = match event.channel_id.message(&ctx, event.id).await {
+            Ok(msg) => msg,
             _ => return, // TODO : log this error
         };
 
-        let msg_time_in_local_tz = msg.timestamp.with_timezone(&tz).naive_local().time();
-        let msg_time_components = TimeComponents::from(msg_time_in_local_tz);
-        let extractor_ctx = TimeExtractorContext::new(tz, msg_time_components);
+        if msg.author.bot {
+            return;
+        }
 
-        let extracted_times: Vec<DateTime<Utc>> = self
-            .time_extractors
-            .iter()
-            .map(|extractor: &TimeExtractor| extractor.as_ref())
-            .flat_map(|extractor| extractor.extract(&msg.content, &extractor_ctx))
-            .unique()
-            .collect();
+        debug!("Edited Message:\n {}: {}", msg.author.name, msg.content);
+
+        let guild_id = match msg.guild_id.or(event.guild_id) {
+            Some(id) => id,
+            _ => return,
+        };
 
-        if let Some(response) = self.construct_response(&extracted_times) {
-            self.reply(&ctx, &msg, &response).await;
+        let reply = self.respond(&ctx, &msg, guild_id).await;
+        match self.replies.reply(msg.id).await {
+            Some(reply_id) if reply.is_empty() => self.retract_reply(&ctx, &msg, reply_id).await,
+            Some(reply_id) => self.edit_reply(&ctx, &msg, reply_id, &reply).await,
+            // The message didn't mention anything before it was edited, or it was answered
+            // too long ago to still be remembered.
+            None if !reply.is_empty() => self.reply(&ctx, &msg, &reply).await,
+            None => {}
         }
     }
 }
@@ -1,5 +1,16 @@
+pub mod clock;
+mod definitions;
+mod embed;
 pub mod extractor;
+mod locale;
+mod markdown;
 mod message_handler;
 pub mod model;
+mod output_zones;
+mod reply_tracker;
+mod zone_commands;
 
+pub use definitions::ExtractorDefinitions;
 pub use message_handler::MessageHandler;
+pub use output_zones::OutputZoneStore;
+pub use zone_commands::ZoneCommandHandler;
@@ -0,0 +1,77 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// A date as it was written in a message. Dates are resolved relative to the author's
+/// current local date, since most written dates ("tomorrow", "Friday") are relative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateComponents {
+    /// A number of days from today, e.g. `1` for "tomorrow".
+    RelativeDays(i64),
+    /// The next occurrence of a weekday, which is today if today is that weekday.
+    Weekday(Weekday),
+    /// A calendar date. Dates without a year refer to their next occurrence.
+    Calendar {
+        month: u32,
+        day: u32,
+        year: Option<i32>,
+    },
+}
+
+impl DateComponents {
+    pub fn resolve(&self, today: NaiveDate) -> Option<NaiveDate> {
+        match *self {
+            DateComponents::RelativeDays(days) => today.checked_add_signed(Duration::days(days)),
+            DateComponents::Weekday(weekday) => {
+                let days_until = (7 + weekday.num_days_from_monday()
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                today.checked_add_signed(Duration::days(days_until.into()))
+            }
+            DateComponents::Calendar {
+                month,
+                day,
+                year: Some(year),
+            } => NaiveDate::from_ymd_opt(year, month, day),
+            DateComponents::Calendar {
+                month,
+                day,
+                year: None,
+            } => {
+                // Feb 29 only exists in leap years, so look a few years ahead for it.
+                (today.year()..=today.year() + 4)
+                    .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+                    .find(|date| *date >= today)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! resolve_data {
+        ($($name:ident($input:expr, $today:expr, $expected:expr),)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = $input.resolve($today);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    resolve_data! {
+        resolve_today(DateComponents::RelativeDays(0), NaiveDate::from_ymd(2021, 3, 4), Some(NaiveDate::from_ymd(2021, 3, 4))),
+        resolve_tomorrow(DateComponents::RelativeDays(1), NaiveDate::from_ymd(2021, 12, 31), Some(NaiveDate::from_ymd(2022, 1, 1))),
+        resolve_yesterday(DateComponents::RelativeDays(-1), NaiveDate::from_ymd(2021, 3, 1), Some(NaiveDate::from_ymd(2021, 2, 28))),
+        resolve_weekday_later_this_week(DateComponents::Weekday(Weekday::Fri), NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2021, 3, 5))),
+        resolve_weekday_next_week(DateComponents::Weekday(Weekday::Mon), NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2021, 3, 8))),
+        resolve_weekday_today(DateComponents::Weekday(Weekday::Wed), NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2021, 3, 3))),
+        resolve_calendar_with_year(DateComponents::Calendar { month: 12, day: 3, year: Some(2020) }, NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2020, 12, 3))),
+        resolve_calendar_this_year(DateComponents::Calendar { month: 12, day: 3, year: None }, NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2021, 12, 3))),
+        resolve_calendar_next_year(DateComponents::Calendar { month: 1, day: 2, year: None }, NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2022, 1, 2))),
+        resolve_calendar_leap_day(DateComponents::Calendar { month: 2, day: 29, year: None }, NaiveDate::from_ymd(2021, 3, 3), Some(NaiveDate::from_ymd(2024, 2, 29))),
+        resolve_calendar_invalid(DateComponents::Calendar { month: 2, day: 30, year: None }, NaiveDate::from_ymd(2021, 3, 3), None),
+    }
+}
@@ -0,0 +1,110 @@
+use super::TimeKind;
+
+/// A part of the day that decides whether a 12-hour time is AM or PM, e.g. "this morning"
+/// or "'s avonds".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayPart {
+    Morning,
+    Afternoon,
+    Evening,
+    Night,
+}
+
+/// The words that name each part of the day in English. A day part phrase is matched by the
+/// first word it contains, so "tonight" and "in the morning" are matched by "night" and
+/// "morning".
+pub const ENGLISH_DAY_PART_WORDS: [(&str, DayPart); 4] = [
+    ("morning", DayPart::Morning),
+    ("afternoon", DayPart::Afternoon),
+    ("evening", DayPart::Evening),
+    ("night", DayPart::Night),
+];
+
+impl DayPart {
+    /// Finds the part of the day named in a phrase, using the first of the words that the
+    /// phrase contains. The phrase is expected to be lowercase.
+    pub fn find<S: AsRef<str>>(phrase: &str, words: &[(S, DayPart)]) -> Option<DayPart> {
+        words
+            .iter()
+            .find(|(word, _)| phrase.contains(word.as_ref()))
+            .map(|(_, day_part)| *day_part)
+    }
+
+    pub fn time_kind(&self, hour: u32) -> TimeKind {
+        match self {
+            DayPart::Morning => TimeKind::AM,
+            DayPart::Afternoon | DayPart::Evening => TimeKind::PM,
+            // "Tonight at 12" or "1 at night" are after midnight, but "tonight at 8" isn't.
+            DayPart::Night => match hour {
+                12 | 1..=4 => TimeKind::AM,
+                _ => TimeKind::PM,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! test_find_data {
+        ($($name:ident{phrase: $input_phrase:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = DayPart::find($input_phrase, &ENGLISH_DAY_PART_WORDS);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_find_data! {
+        test_find_morning {
+            phrase: "in the morning",
+            expected: Some(DayPart::Morning),
+        },
+        test_find_tonight {
+            phrase: "tonight",
+            expected: Some(DayPart::Night),
+        },
+        test_find_unknown {
+            phrase: "at lunch",
+            expected: None,
+        },
+    }
+
+    macro_rules! test_time_kind_data {
+        ($($name:ident{day_part: $input_day_part:expr, hour: $input_hour:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                assert_eq!($input_day_part.time_kind($input_hour), $expected);
+            }
+            )*
+        };
+    }
+
+    test_time_kind_data! {
+        test_time_kind_morning {
+            day_part: DayPart::Morning,
+            hour: 9,
+            expected: TimeKind::AM,
+        },
+        test_time_kind_evening {
+            day_part: DayPart::Evening,
+            hour: 8,
+            expected: TimeKind::PM,
+        },
+        test_time_kind_late_night {
+            day_part: DayPart::Night,
+            hour: 11,
+            expected: TimeKind::PM,
+        },
+        test_time_kind_after_midnight {
+            day_part: DayPart::Night,
+            hour: 2,
+            expected: TimeKind::AM,
+        },
+    }
+}
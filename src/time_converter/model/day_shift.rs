@@ -0,0 +1,101 @@
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// How many days a converted time is ahead of or behind the author's local date, e.g. "11pm"
+/// in Los Angeles being the next morning in Amsterdam.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DayShift {
+    days: i64,
+    weekday: Weekday,
+}
+
+#[allow(dead_code)]
+impl DayShift {
+    /// Compares the date of an instant in a zone to its date in the author's local zone, which
+    /// gives nothing when both are the same day.
+    pub fn between(instant: &DateTime<Utc>, tz: &Tz, local_tz: &Tz) -> Option<Self> {
+        let date = instant.with_timezone(tz).naive_local().date();
+        let local_date = instant.with_timezone(local_tz).naive_local().date();
+
+        let days = (date - local_date).num_days();
+        if days == 0 {
+            return None;
+        }
+
+        Some(Self {
+            days,
+            weekday: date.weekday(),
+        })
+    }
+
+    pub fn days(&self) -> i64 {
+        self.days
+    }
+
+    /// The day of the week of the converted time.
+    pub fn weekday(&self) -> Weekday {
+        self.weekday
+    }
+}
+
+impl fmt::Display for DayShift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.days > 0 { '+' } else { '−' };
+        let unit = if self.days.abs() == 1 { "day" } else { "days" };
+        write!(f, "{}{} {}", sign, self.days.abs(), unit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+    use chrono_tz::{America, Europe, Pacific};
+
+    use super::*;
+
+    macro_rules! test_between_data {
+        ($($name:ident{instant: $input_instant:expr, tz: $input_tz:expr, local_tz: $input_local_tz:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = DayShift::between(&$input_instant, &$input_tz, &$input_local_tz)
+                    .map(|shift| (shift.to_string(), shift.weekday()));
+                let expected = $expected.map(|(text, weekday): (&str, Weekday)| (String::from(text), weekday));
+                assert_eq!(actual, expected);
+            }
+            )*
+        };
+    }
+
+    test_between_data! {
+        test_between_same_day {
+            instant: Utc.ymd(2021, 3, 4).and_hms(12, 0, 0),
+            tz: Europe::Amsterdam,
+            local_tz: America::New_York,
+            expected: None,
+        },
+        test_between_next_day {
+            // 11pm in Los Angeles on Wednesday
+            instant: Utc.ymd(2021, 3, 4).and_hms(7, 0, 0),
+            tz: Europe::Amsterdam,
+            local_tz: America::Los_Angeles,
+            expected: Some(("+1 day", Weekday::Thu)),
+        },
+        test_between_previous_day {
+            // 1am in Amsterdam on Thursday
+            instant: Utc.ymd(2021, 3, 4).and_hms(0, 0, 0),
+            tz: America::Los_Angeles,
+            local_tz: Europe::Amsterdam,
+            expected: Some(("−1 day", Weekday::Wed)),
+        },
+        test_between_across_date_line {
+            // 12:30am in Kiritimati on Thursday, which is still Tuesday in Pago Pago
+            instant: Utc.ymd(2021, 3, 3).and_hms(10, 30, 0),
+            tz: Pacific::Pago_Pago,
+            local_tz: Pacific::Kiritimati,
+            expected: Some(("−2 days", Weekday::Tue)),
+        },
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+/// How Discord renders a timestamp in each reader's own locale and time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(dead_code)]
+pub enum TimestampStyle {
+    ShortTime,
+    LongTime,
+    ShortDate,
+    LongDate,
+    ShortDateTime,
+    LongDateTime,
+    Relative,
+}
+
+impl TimestampStyle {
+    pub fn flag(&self) -> char {
+        use TimestampStyle::*;
+
+        match *self {
+            ShortTime => 't',
+            LongTime => 'T',
+            ShortDate => 'd',
+            LongDate => 'D',
+            ShortDateTime => 'f',
+            LongDateTime => 'F',
+            Relative => 'R',
+        }
+    }
+
+    pub fn from_flag(flag: char) -> Option<Self> {
+        use TimestampStyle::*;
+
+        match flag {
+            't' => Some(ShortTime),
+            'T' => Some(LongTime),
+            'd' => Some(ShortDate),
+            'D' => Some(LongDate),
+            'f' => Some(ShortDateTime),
+            'F' => Some(LongDateTime),
+            'R' => Some(Relative),
+            _ => None,
+        }
+    }
+}
+
+impl Default for TimestampStyle {
+    /// The style Discord uses when the markup doesn't specify one.
+    fn default() -> Self {
+        TimestampStyle::ShortDateTime
+    }
+}
+
+/// Discord's timestamp markup, e.g. `<t:1700000000:F>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiscordTimestamp {
+    instant: DateTime<Utc>,
+    style: TimestampStyle,
+}
+
+#[allow(dead_code)]
+impl DiscordTimestamp {
+    pub fn new(instant: DateTime<Utc>, style: TimestampStyle) -> Self {
+        Self { instant, style }
+    }
+
+    pub fn instant(&self) -> DateTime<Utc> {
+        self.instant
+    }
+
+    pub fn style(&self) -> TimestampStyle {
+        self.style
+    }
+}
+
+impl fmt::Display for DiscordTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<t:{}:{}>", self.instant.timestamp(), self.style.flag())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    macro_rules! display_data {
+        ($($name:ident($style:expr, $expected:expr),)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let instant = Utc.timestamp(1_700_000_000, 0);
+                let actual = DiscordTimestamp::new(instant, $style).to_string();
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    display_data! {
+        display_short_time(TimestampStyle::ShortTime, "<t:1700000000:t>"),
+        display_long_time(TimestampStyle::LongTime, "<t:1700000000:T>"),
+        display_short_date(TimestampStyle::ShortDate, "<t:1700000000:d>"),
+        display_long_date(TimestampStyle::LongDate, "<t:1700000000:D>"),
+        display_short_date_time(TimestampStyle::ShortDateTime, "<t:1700000000:f>"),
+        display_long_date_time(TimestampStyle::LongDateTime, "<t:1700000000:F>"),
+        display_relative(TimestampStyle::Relative, "<t:1700000000:R>"),
+    }
+
+    #[test]
+    fn style_flags_round_trip() {
+        use TimestampStyle::*;
+
+        for style in &[
+            ShortTime,
+            LongTime,
+            ShortDate,
+            LongDate,
+            ShortDateTime,
+            LongDateTime,
+            Relative,
+        ] {
+            assert_eq!(TimestampStyle::from_flag(style.flag()), Some(*style));
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+
+use super::LocalInstant;
+
+/// A point in time, or range of time, extracted from a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtractedTime {
+    Instant(LocalInstant),
+    /// A point in time where AM/PM wasn't written and had to be inferred.
+    Inferred(LocalInstant),
+    Range {
+        start: LocalInstant,
+        end: LocalInstant,
+    },
+}
+
+impl ExtractedTime {
+    pub fn start(&self) -> DateTime<Utc> {
+        match *self {
+            ExtractedTime::Instant(instant) | ExtractedTime::Inferred(instant) => {
+                instant.earliest()
+            }
+            ExtractedTime::Range { start, .. } => start.earliest(),
+        }
+    }
+
+    pub fn end(&self) -> Option<DateTime<Utc>> {
+        match *self {
+            ExtractedTime::Instant(_) | ExtractedTime::Inferred(_) => None,
+            ExtractedTime::Range { end, .. } => Some(end.earliest()),
+        }
+    }
+
+    /// The instants that make up the time, which is both ends of a range.
+    pub fn instants(&self) -> Vec<LocalInstant> {
+        match *self {
+            ExtractedTime::Instant(instant) | ExtractedTime::Inferred(instant) => vec![instant],
+            ExtractedTime::Range { start, end } => vec![start, end],
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for ExtractedTime {
+    fn from(value: DateTime<Utc>) -> Self {
+        ExtractedTime::Instant(LocalInstant::Single(value))
+    }
+}
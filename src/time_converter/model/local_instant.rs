@@ -0,0 +1,129 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+
+/// The instant that a local time refers to, which isn't clear when the clocks are changed for
+/// daylight saving time around that time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalInstant {
+    Single(DateTime<Utc>),
+    /// A local time that happens twice, because the clocks are turned back at that time, so it
+    /// could be either instant.
+    Repeated {
+        earlier: DateTime<Utc>,
+        later: DateTime<Utc>,
+    },
+    /// A local time that doesn't exist, because the clocks are turned forward past it. It's
+    /// either the instant that it would have been with the offset from before the clocks were
+    /// turned forward, or with the offset from after.
+    Skipped {
+        earlier: DateTime<Utc>,
+        later: DateTime<Utc>,
+    },
+}
+
+impl LocalInstant {
+    /// Interprets a local time in a zone, which is repeated or skipped when the zone's clocks
+    /// are turned back or forward around that time.
+    pub fn from_local<Tz: TimeZone>(tz: &Tz, local: &NaiveDateTime) -> Self {
+        match tz.from_local_datetime(local) {
+            LocalResult::Single(instant) => LocalInstant::Single(instant.with_timezone(&Utc)),
+            LocalResult::Ambiguous(first, second) => {
+                let (earlier, later) =
+                    ordered(first.with_timezone(&Utc), second.with_timezone(&Utc));
+                LocalInstant::Repeated { earlier, later }
+            }
+            LocalResult::None => {
+                // The clocks are only turned forward once around a time, so the offsets a day
+                // before and after are the ones from either side of the skipped hours.
+                let with_offset_at = |utc: NaiveDateTime| {
+                    let offset = tz.offset_from_utc_datetime(&utc).fix();
+                    let instant = *local - Duration::seconds(offset.local_minus_utc().into());
+                    DateTime::<Utc>::from_utc(instant, Utc)
+                };
+                let (earlier, later) = ordered(
+                    with_offset_at(*local - Duration::days(1)),
+                    with_offset_at(*local + Duration::days(1)),
+                );
+                LocalInstant::Skipped { earlier, later }
+            }
+        }
+    }
+
+    /// The earliest instant that the local time could refer to.
+    pub fn earliest(&self) -> DateTime<Utc> {
+        match *self {
+            LocalInstant::Single(instant) => instant,
+            LocalInstant::Repeated { earlier, .. } | LocalInstant::Skipped { earlier, .. } => {
+                earlier
+            }
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for LocalInstant {
+    fn from(value: DateTime<Utc>) -> Self {
+        LocalInstant::Single(value)
+    }
+}
+
+fn ordered(first: DateTime<Utc>, second: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    if first <= second {
+        (first, second)
+    } else {
+        (second, first)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use chrono_tz::{America, Europe};
+
+    use super::*;
+
+    macro_rules! test_from_local_data {
+        ($($name:ident{tz: $input_tz:expr, local: $input_local:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = LocalInstant::from_local(&$input_tz, &$input_local);
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_from_local_data! {
+        test_from_local_single {
+            tz: America::New_York,
+            local: NaiveDate::from_ymd(2021, 3, 14).and_hms(1, 30, 0),
+            expected: LocalInstant::Single(Utc.ymd(2021, 3, 14).and_hms(6, 30, 0)),
+        },
+        test_from_local_skipped {
+            tz: America::New_York,
+            local: NaiveDate::from_ymd(2021, 3, 14).and_hms(2, 30, 0),
+            expected: LocalInstant::Skipped {
+                // 2:30am EDT and 2:30am EST
+                earlier: Utc.ymd(2021, 3, 14).and_hms(6, 30, 0),
+                later: Utc.ymd(2021, 3, 14).and_hms(7, 30, 0),
+            },
+        },
+        test_from_local_repeated {
+            tz: America::New_York,
+            local: NaiveDate::from_ymd(2021, 11, 7).and_hms(1, 30, 0),
+            expected: LocalInstant::Repeated {
+                // 1:30am EDT and 1:30am EST
+                earlier: Utc.ymd(2021, 11, 7).and_hms(5, 30, 0),
+                later: Utc.ymd(2021, 11, 7).and_hms(6, 30, 0),
+            },
+        },
+        test_from_local_skipped_europe {
+            tz: Europe::Amsterdam,
+            local: NaiveDate::from_ymd(2021, 3, 28).and_hms(2, 15, 0),
+            expected: LocalInstant::Skipped {
+                // 2:15am CEST and 2:15am CET
+                earlier: Utc.ymd(2021, 3, 28).and_hms(0, 15, 0),
+                later: Utc.ymd(2021, 3, 28).and_hms(1, 15, 0),
+            },
+        },
+    }
+}
@@ -1,3 +1,19 @@
+mod date_components;
+mod day_part;
+mod day_shift;
+mod discord_timestamp;
+mod extracted_time;
+mod local_instant;
 mod time_components;
+mod time_expression;
+mod time_range;
 
+pub use date_components::DateComponents;
+pub use day_part::{DayPart, ENGLISH_DAY_PART_WORDS};
+pub use day_shift::DayShift;
+pub use discord_timestamp::{DiscordTimestamp, TimestampStyle};
+pub use extracted_time::ExtractedTime;
+pub use local_instant::LocalInstant;
 pub use time_components::{TimeComponents, TimeKind};
+pub use time_expression::TimeExpression;
+pub use time_range::TimeRange;
@@ -52,6 +52,18 @@ impl TimeComponents {
         TimeComponents { hour, minute, kind }
     }
 
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    pub fn kind(&self) -> TimeKind {
+        self.kind
+    }
+
     fn validate(self) -> TimeResult<Self> {
         use TimeRepresentationError::OutOfBounds;
         let TimeComponents { hour, minute, kind } = self;
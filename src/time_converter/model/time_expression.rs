@@ -0,0 +1,31 @@
+use chrono_tz::Tz;
+
+use super::TimeComponents;
+
+/// A time as it was written in a message, along with the time zone it was explicitly
+/// written in, if any. Times without a zone are interpreted in the author's local zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeExpression {
+    time: TimeComponents,
+    zone: Option<Tz>,
+}
+
+impl TimeExpression {
+    pub fn new(time: TimeComponents, zone: Option<Tz>) -> Self {
+        Self { time, zone }
+    }
+
+    pub fn time(&self) -> TimeComponents {
+        self.time
+    }
+
+    pub fn zone(&self) -> Option<Tz> {
+        self.zone
+    }
+}
+
+impl From<TimeComponents> for TimeExpression {
+    fn from(value: TimeComponents) -> Self {
+        TimeExpression::new(value, None)
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::NaiveTime;
+
+use super::{time_components::TimeRepresentationError, TimeComponents, TimeKind};
+
+/// A range of times as written in a message, such as "5-7pm" or "10pm-2am".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeRange {
+    start: TimeComponents,
+    end: TimeComponents,
+}
+
+impl TimeRange {
+    /// Creates a range whose start may omit its time kind, in which case it's inherited
+    /// from the end of the range, so "5-7pm" starts at 5pm. If inheriting the time kind
+    /// would place the start after the end, the other half of the day is used instead,
+    /// so "11-1pm" starts at 11am.
+    pub fn new(
+        start_hour: u32,
+        start_minute: u32,
+        start_kind: Option<TimeKind>,
+        end: TimeComponents,
+    ) -> Result<Self, TimeRepresentationError> {
+        let start = match (start_kind, end.kind()) {
+            (Some(kind), _) => TimeComponents::new(start_hour, start_minute, kind)?,
+            (None, TimeKind::Military) => {
+                TimeComponents::new(start_hour, start_minute, TimeKind::Military)?
+            }
+            (None, end_kind) => {
+                let inherited = TimeComponents::new(start_hour, start_minute, end_kind)?;
+                let opposite_kind = match end_kind {
+                    TimeKind::AM => TimeKind::PM,
+                    _ => TimeKind::AM,
+                };
+
+                if NaiveTime::from(inherited) <= NaiveTime::from(end) {
+                    inherited
+                } else {
+                    TimeComponents::new(start_hour, start_minute, opposite_kind)?
+                }
+            }
+        };
+
+        Ok(TimeRange { start, end })
+    }
+
+    pub fn start(&self) -> TimeComponents {
+        self.start
+    }
+
+    pub fn end(&self) -> TimeComponents {
+        self.end
+    }
+
+    /// Whether the range ends on the day after it starts, like "10pm-2am".
+    pub fn crosses_midnight(&self) -> bool {
+        NaiveTime::from(self.end) < NaiveTime::from(self.start)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! time_range_data {
+        ($($name:ident($start_hour:expr, $start_minute:expr, $start_kind:expr, $end:expr, $expected_start:expr, $expected_crosses_midnight:expr),)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = TimeRange::new($start_hour, $start_minute, $start_kind, $end).unwrap();
+                assert_eq!(actual.start(), $expected_start);
+                assert_eq!(actual.end(), $end);
+                assert_eq!(actual.crosses_midnight(), $expected_crosses_midnight);
+            }
+            )*
+        };
+    }
+
+    time_range_data! {
+        range_inherits_pm(5, 0, None, TimeComponents::of(7, 0, TimeKind::PM), TimeComponents::of(5, 0, TimeKind::PM), false),
+        range_inherits_am(9, 30, None, TimeComponents::of(11, 0, TimeKind::AM), TimeComponents::of(9, 30, TimeKind::AM), false),
+        range_inherits_noon(12, 0, None, TimeComponents::of(2, 0, TimeKind::PM), TimeComponents::of(12, 0, TimeKind::PM), false),
+        range_flips_to_am(11, 0, None, TimeComponents::of(1, 0, TimeKind::PM), TimeComponents::of(11, 0, TimeKind::AM), false),
+        range_flips_to_pm_across_midnight(10, 0, None, TimeComponents::of(2, 0, TimeKind::AM), TimeComponents::of(10, 0, TimeKind::PM), true),
+        range_explicit_kinds(5, 0, Some(TimeKind::AM), TimeComponents::of(6, 0, TimeKind::PM), TimeComponents::of(5, 0, TimeKind::AM), false),
+        range_explicit_kinds_across_midnight(11, 0, Some(TimeKind::PM), TimeComponents::of(1, 30, TimeKind::AM), TimeComponents::of(11, 0, TimeKind::PM), true),
+        range_military(17, 0, None, TimeComponents::of(19, 30, TimeKind::Military), TimeComponents::of(17, 0, TimeKind::Military), false),
+        range_military_across_midnight(22, 0, None, TimeComponents::of(1, 0, TimeKind::Military), TimeComponents::of(22, 0, TimeKind::Military), true),
+    }
+
+    #[test]
+    fn range_invalid_start() {
+        let actual = TimeRange::new(17, 0, None, TimeComponents::of(7, 0, TimeKind::PM));
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,473 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serenity::model::id::GuildId;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+/// The most zones a guild can convert times to, which keeps replies readable.
+pub const MAX_OUTPUT_ZONES: usize = 10;
+
+pub type OutputZoneResult<T> = Result<T, OutputZoneError>;
+
+#[derive(Error, Debug)]
+pub enum OutputZoneError {
+    #[error("There's already a zone labelled [{label}].")]
+    DuplicateLabel { label: String },
+    #[error("There's no zone labelled [{label}].")]
+    UnknownLabel { label: String },
+    #[error("Position [{position}] is out of range, expected 1 to {len}.")]
+    InvalidPosition { position: usize, len: usize },
+    #[error("A guild can't have more than {} zones.", MAX_OUTPUT_ZONES)]
+    TooManyZones,
+    #[error("Can't remove [{label}], a guild needs at least one zone.")]
+    LastZone { label: String },
+    #[error("Failed to read output zones from [{path}]: {source}")]
+    Read { path: String, source: io::Error },
+    #[error("Failed to write output zones to [{path}]: {source}")]
+    Write { path: String, source: io::Error },
+    #[error("Failed to parse output zones from [{path}]: {source}")]
+    Parse {
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+/// A zone that converted times are shown in, under a label such as "UK".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputZone {
+    label: String,
+    #[serde(with = "tz_name")]
+    zone: Tz,
+}
+
+impl OutputZone {
+    pub fn new(label: &str, zone: Tz) -> Self {
+        Self {
+            label: String::from(label.trim()),
+            zone,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn zone(&self) -> Tz {
+        self.zone
+    }
+}
+
+/// The zones of a guild, in the order they're shown in. Labels are unique, ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OutputZones {
+    zones: Vec<OutputZone>,
+}
+
+#[allow(dead_code)]
+impl OutputZones {
+    pub fn new(zones: Vec<OutputZone>) -> Self {
+        Self { zones }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &OutputZone> {
+        self.zones.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// Adds a zone after the others.
+    pub fn add(&mut self, zone: OutputZone) -> OutputZoneResult<()> {
+        if self.position(zone.label()).is_some() {
+            return Err(OutputZoneError::DuplicateLabel { label: zone.label });
+        }
+        if self.zones.len() >= MAX_OUTPUT_ZONES {
+            return Err(OutputZoneError::TooManyZones);
+        }
+
+        self.zones.push(zone);
+        Ok(())
+    }
+
+    /// Removes a zone, as long as it isn't the only one left, since times would otherwise have
+    /// nothing to be converted to.
+    pub fn remove(&mut self, label: &str) -> OutputZoneResult<OutputZone> {
+        let index = self.index_of(label)?;
+        if self.zones.len() == 1 {
+            return Err(OutputZoneError::LastZone {
+                label: self.zones[index].label.clone(),
+            });
+        }
+        Ok(self.zones.remove(index))
+    }
+
+    /// Moves a zone to a position, counting from 1.
+    pub fn move_to(&mut self, label: &str, position: usize) -> OutputZoneResult<()> {
+        let index = self.index_of(label)?;
+        if !(1..=self.zones.len()).contains(&position) {
+            return Err(OutputZoneError::InvalidPosition {
+                position,
+                len: self.zones.len(),
+            });
+        }
+
+        let zone = self.zones.remove(index);
+        self.zones.insert(position - 1, zone);
+        Ok(())
+    }
+
+    fn position(&self, label: &str) -> Option<usize> {
+        let label = label.trim();
+        self.zones
+            .iter()
+            .position(|zone| zone.label().eq_ignore_ascii_case(label))
+    }
+
+    fn index_of(&self, label: &str) -> OutputZoneResult<usize> {
+        self.position(label)
+            .ok_or_else(|| OutputZoneError::UnknownLabel {
+                label: String::from(label.trim()),
+            })
+    }
+}
+
+impl Default for OutputZones {
+    /// The zones for guilds that haven't set their own.
+    fn default() -> Self {
+        Self::new(vec![
+            OutputZone::new("Netherlands", chrono_tz::Europe::Amsterdam),
+            OutputZone::new("UK", chrono_tz::Europe::London),
+            OutputZone::new("US East", chrono_tz::America::New_York),
+            OutputZone::new("US West", chrono_tz::America::Los_Angeles),
+        ])
+    }
+}
+
+/// The output zones of each guild, which are saved to a file whenever they change so that
+/// they're kept across restarts. Without a file, changes are only kept in memory.
+#[derive(Debug)]
+pub struct OutputZoneStore {
+    path: Option<String>,
+    guild_zones: RwLock<HashMap<GuildId, OutputZones>>,
+}
+
+#[allow(dead_code)]
+impl OutputZoneStore {
+    /// Loads the zones from the file, which doesn't have to exist yet.
+    pub fn load(path: Option<&str>) -> OutputZoneResult<Self> {
+        let guild_zones = match path {
+            Some(path) if Path::new(path).exists() => {
+                let text = fs::read_to_string(path).map_err(|source| OutputZoneError::Read {
+                    path: String::from(path),
+                    source,
+                })?;
+                let file: HashMap<u64, OutputZones> =
+                    serde_json::from_str(&text).map_err(|source| OutputZoneError::Parse {
+                        path: String::from(path),
+                        source,
+                    })?;
+                file.into_iter()
+                    .map(|(guild_id, zones)| (GuildId(guild_id), zones))
+                    .collect()
+            }
+            _ => HashMap::new(),
+        };
+
+        Ok(Self {
+            path: path.map(String::from),
+            guild_zones: RwLock::new(guild_zones),
+        })
+    }
+
+    /// The zones of the guild, or the default zones if it hasn't set its own. A saved list
+    /// that's been emptied by hand also falls back to the default zones.
+    pub async fn zones(&self, guild_id: GuildId) -> OutputZones {
+        let guild_zones = self.guild_zones.read().await;
+        guild_zones
+            .get(&guild_id)
+            .filter(|zones| !zones.is_empty())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Changes the zones of the guild, which are only kept if the change succeeds and the
+    /// zones could be saved.
+    pub async fn update<T>(
+        &self,
+        guild_id: GuildId,
+        change: impl FnOnce(&mut OutputZones) -> OutputZoneResult<T>,
+    ) -> OutputZoneResult<T> {
+        let mut guild_zones = self.guild_zones.write().await;
+
+        let mut zones = guild_zones.get(&guild_id).cloned().unwrap_or_default();
+        let result = change(&mut zones)?;
+
+        let mut changed = guild_zones.clone();
+        changed.insert(guild_id, zones);
+        self.save(&changed).await?;
+
+        *guild_zones = changed;
+        Ok(result)
+    }
+
+    /// Goes back to the default zones for the guild.
+    pub async fn reset(&self, guild_id: GuildId) -> OutputZoneResult<()> {
+        let mut guild_zones = self.guild_zones.write().await;
+
+        let mut changed = guild_zones.clone();
+        changed.remove(&guild_id);
+        self.save(&changed).await?;
+
+        *guild_zones = changed;
+        Ok(())
+    }
+
+    async fn save(&self, guild_zones: &HashMap<GuildId, OutputZones>) -> OutputZoneResult<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let file: HashMap<u64, &OutputZones> = guild_zones
+            .iter()
+            .map(|(guild_id, zones)| (guild_id.0, zones))
+            .collect();
+        let text =
+            serde_json::to_string_pretty(&file).expect("Expected output zones to be serializable.");
+
+        // Write to a temporary file first, so a failed write doesn't lose the saved zones.
+        let temporary_path = format!("{}.tmp", path);
+        let written = match tokio::fs::write(&temporary_path, text).await {
+            Ok(_) => tokio::fs::rename(&temporary_path, path).await,
+            Err(error) => Err(error),
+        };
+        written.map_err(|source| OutputZoneError::Write {
+            path: path.clone(),
+            source,
+        })
+    }
+}
+
+/// Saves zones by their IANA name, such as "Europe/London".
+mod tz_name {
+    use chrono_tz::Tz;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(zone: &Tz, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(zone.name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Tz, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use chrono_tz::{America, Asia, Europe};
+
+    use super::*;
+
+    fn zones() -> OutputZones {
+        OutputZones::new(vec![
+            OutputZone::new("UK", Europe::London),
+            OutputZone::new("US East", America::New_York),
+            OutputZone::new("Japan", Asia::Tokyo),
+        ])
+    }
+
+    fn labels(zones: &OutputZones) -> Vec<&str> {
+        zones.iter().map(OutputZone::label).collect()
+    }
+
+    #[test]
+    fn test_add() {
+        let mut zones = zones();
+        zones
+            .add(OutputZone::new(" India ", Asia::Kolkata))
+            .unwrap();
+        assert_eq!(labels(&zones), vec!["UK", "US East", "Japan", "India"]);
+    }
+
+    #[test]
+    fn test_add_duplicate_label() {
+        let error = zones()
+            .add(OutputZone::new("us east", America::Detroit))
+            .unwrap_err();
+        assert!(matches!(error, OutputZoneError::DuplicateLabel { label } if label == "us east"));
+    }
+
+    #[test]
+    fn test_add_too_many() {
+        let mut zones = OutputZones::new(Vec::new());
+        for index in 0..MAX_OUTPUT_ZONES {
+            zones
+                .add(OutputZone::new(&index.to_string(), Europe::London))
+                .unwrap();
+        }
+        let error = zones
+            .add(OutputZone::new("One more", Europe::London))
+            .unwrap_err();
+        assert!(matches!(error, OutputZoneError::TooManyZones));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut zones = zones();
+        let removed = zones.remove("us EAST").unwrap();
+        assert_eq!(removed, OutputZone::new("US East", America::New_York));
+        assert_eq!(labels(&zones), vec!["UK", "Japan"]);
+    }
+
+    #[test]
+    fn test_remove_unknown_label() {
+        let error = zones().remove("France").unwrap_err();
+        assert!(matches!(error, OutputZoneError::UnknownLabel { label } if label == "France"));
+    }
+
+    #[test]
+    fn test_remove_last_zone() {
+        let mut zones = OutputZones::new(vec![OutputZone::new("UK", Europe::London)]);
+        let error = zones.remove("uk").unwrap_err();
+        assert!(matches!(error, OutputZoneError::LastZone { label } if label == "UK"));
+        assert_eq!(labels(&zones), vec!["UK"]);
+    }
+
+    macro_rules! test_move_to_data {
+        ($($name:ident{label: $input_label:expr, position: $input_position:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let mut zones = zones();
+                zones.move_to($input_label, $input_position).unwrap();
+                let expected: Vec<&str> = $expected;
+                assert_eq!(labels(&zones), expected);
+            }
+            )*
+        };
+    }
+
+    test_move_to_data! {
+        test_move_to_first {
+            label: "Japan",
+            position: 1,
+            expected: vec!["Japan", "UK", "US East"],
+        },
+        test_move_to_last {
+            label: "UK",
+            position: 3,
+            expected: vec!["US East", "Japan", "UK"],
+        },
+        test_move_to_same_position {
+            label: "US East",
+            position: 2,
+            expected: vec!["UK", "US East", "Japan"],
+        },
+    }
+
+    #[test]
+    fn test_move_to_invalid_position() {
+        let error = zones().move_to("UK", 4).unwrap_err();
+        assert!(matches!(
+            error,
+            OutputZoneError::InvalidPosition {
+                position: 4,
+                len: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_unknown_zone() {
+        let result: Result<OutputZones, _> =
+            serde_json::from_str(r#"[{"label": "Nowhere", "zone": "Europe/Nowhere"}]"#);
+        assert!(result.is_err());
+    }
+
+    fn temporary_path(name: &str) -> String {
+        let path =
+            env::temp_dir().join(format!("output_zones_{}_{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path.to_string_lossy().into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_store_defaults() {
+        let store = OutputZoneStore::load(None).unwrap();
+        assert_eq!(store.zones(GuildId(1)).await, OutputZones::default());
+    }
+
+    #[tokio::test]
+    async fn test_store_defaults_for_empty_zones() {
+        let path = temporary_path("empty");
+        fs::write(&path, r#"{"1": []}"#).unwrap();
+
+        let store = OutputZoneStore::load(Some(&path)).unwrap();
+        assert_eq!(store.zones(GuildId(1)).await, OutputZones::default());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_store_keeps_zones_across_loads() {
+        let path = temporary_path("reload");
+
+        let store = OutputZoneStore::load(Some(&path)).unwrap();
+        store
+            .update(GuildId(1), |zones| {
+                zones.add(OutputZone::new("Japan", Asia::Tokyo))?;
+                zones.move_to("Japan", 1)
+            })
+            .await
+            .unwrap();
+
+        let reloaded = OutputZoneStore::load(Some(&path)).unwrap();
+        assert_eq!(
+            labels(&reloaded.zones(GuildId(1)).await),
+            vec!["Japan", "Netherlands", "UK", "US East", "US West"]
+        );
+        assert_eq!(reloaded.zones(GuildId(2)).await, OutputZones::default());
+
+        reloaded.reset(GuildId(1)).await.unwrap();
+        let reloaded = OutputZoneStore::load(Some(&path)).unwrap();
+        assert_eq!(reloaded.zones(GuildId(1)).await, OutputZones::default());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_store_failed_update_keeps_zones() {
+        let store = OutputZoneStore::load(None).unwrap();
+        let result = store
+            .update(GuildId(1), |zones| {
+                zones.remove("UK")?;
+                zones.remove("France")
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(store.zones(GuildId(1)).await, OutputZones::default());
+    }
+
+    #[test]
+    fn test_load_invalid_file() {
+        let path = temporary_path("invalid");
+        fs::write(&path, "not json").unwrap();
+
+        let error = OutputZoneStore::load(Some(&path)).unwrap_err();
+        assert!(matches!(error, OutputZoneError::Parse { .. }));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, VecDeque};
+
+use serenity::model::id::MessageId;
+use tokio::sync::RwLock;
+
+/// The number of replies that are remembered by default. Only recent messages are likely to
+/// be edited, so the oldest replies are forgotten past this.
+const MAX_TRACKED_REPLIES: usize = 1000;
+
+/// Remembers which reply was sent to which message, so a reply can be updated when the
+/// message it answers is edited. Replies are in the same channel as their message.
+#[derive(Debug)]
+pub struct ReplyTracker {
+    capacity: usize,
+    replies: RwLock<TrackedReplies>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedReplies {
+    by_source: HashMap<MessageId, MessageId>,
+    // The order the messages were answered in, oldest first.
+    sources: VecDeque<MessageId>,
+}
+
+impl ReplyTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            replies: RwLock::new(TrackedReplies::default()),
+        }
+    }
+
+    /// Remembers the reply to a message, forgetting the oldest reply when there are too many.
+    pub async fn track(&self, source: MessageId, reply: MessageId) {
+        let mut replies = self.replies.write().await;
+        if replies.by_source.insert(source, reply).is_none() {
+            replies.sources.push_back(source);
+        }
+
+        while replies.sources.len() > self.capacity {
+            if let Some(oldest) = replies.sources.pop_front() {
+                replies.by_source.remove(&oldest);
+            }
+        }
+    }
+
+    pub async fn reply(&self, source: MessageId) -> Option<MessageId> {
+        self.replies.read().await.by_source.get(&source).copied()
+    }
+
+    /// Forgets the reply to a message, such as when the reply is deleted.
+    pub async fn forget(&self, source: MessageId) -> Option<MessageId> {
+        let mut replies = self.replies.write().await;
+        let reply = replies.by_source.remove(&source)?;
+        replies.sources.retain(|tracked| *tracked != source);
+        Some(reply)
+    }
+}
+
+impl Default for ReplyTracker {
+    fn default() -> Self {
+        Self::new(MAX_TRACKED_REPLIES)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_track() {
+        let tracker = ReplyTracker::default();
+        tracker.track(MessageId(1), MessageId(2)).await;
+
+        assert_eq!(tracker.reply(MessageId(1)).await, Some(MessageId(2)));
+        assert_eq!(tracker.reply(MessageId(2)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_track_past_capacity() {
+        let tracker = ReplyTracker::new(2);
+        tracker.track(MessageId(1), MessageId(10)).await;
+        tracker.track(MessageId(2), MessageId(20)).await;
+        tracker.track(MessageId(3), MessageId(30)).await;
+
+        assert_eq!(tracker.reply(MessageId(1)).await, None);
+        assert_eq!(tracker.reply(MessageId(2)).await, Some(MessageId(20)));
+        assert_eq!(tracker.reply(MessageId(3)).await, Some(MessageId(30)));
+    }
+
+    #[tokio::test]
+    async fn test_track_again() {
+        let tracker = ReplyTracker::new(2);
+        tracker.track(MessageId(1), MessageId(10)).await;
+        tracker.track(MessageId(1), MessageId(11)).await;
+        tracker.track(MessageId(2), MessageId(20)).await;
+
+        assert_eq!(tracker.reply(MessageId(1)).await, Some(MessageId(11)));
+        assert_eq!(tracker.reply(MessageId(2)).await, Some(MessageId(20)));
+    }
+
+    #[tokio::test]
+    async fn test_forget() {
+        let tracker = ReplyTracker::new(2);
+        tracker.track(MessageId(1), MessageId(10)).await;
+        tracker.track(MessageId(2), MessageId(20)).await;
+
+        assert_eq!(tracker.forget(MessageId(1)).await, Some(MessageId(10)));
+        assert_eq!(tracker.forget(MessageId(1)).await, None);
+
+        // Forgotten replies don't count towards the capacity.
+        tracker.track(MessageId(3), MessageId(30)).await;
+        assert_eq!(tracker.reply(MessageId(2)).await, Some(MessageId(20)));
+    }
+}
@@ -0,0 +1,520 @@
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+use log::{error, info};
+use serde_json::{json, Value};
+use serenity::{
+    async_trait,
+    client::{Context, EventHandler},
+    model::{
+        gateway::Ready,
+        id::GuildId,
+        interactions::{
+            ApplicationCommandInteractionData, ApplicationCommandInteractionDataOption,
+            Interaction, InteractionType,
+        },
+    },
+    utils::MessageBuilder,
+};
+use thiserror::Error;
+
+use super::output_zones::{OutputZone, OutputZoneError, OutputZoneStore, OutputZones};
+
+const COMMAND_NAME: &str = "zones";
+
+type ZoneCommandResult<T> = Result<T, ZoneCommandError>;
+
+#[derive(Error, Debug)]
+pub enum ZoneCommandError {
+    #[error("Unknown command [{name}].")]
+    UnknownCommand { name: String },
+    #[error("The {name} option is missing.")]
+    MissingOption { name: &'static str },
+    #[error("The {name} option is invalid.")]
+    InvalidOption { name: &'static str },
+    #[error("Unknown time zone [{zone}], expected a name such as Europe/London.")]
+    UnknownZone { zone: String },
+    #[error("Only server managers can change the zones.")]
+    NotAllowed,
+    #[error("{0}")]
+    OutputZone(#[from] OutputZoneError),
+}
+
+/// The subcommands of the zones command, which manage the zones that converted times are
+/// shown in for a guild.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ZoneCommand {
+    List,
+    Add { label: String, zone: Tz },
+    Remove { label: String },
+    Move { label: String, position: usize },
+    Reset,
+}
+
+impl ZoneCommand {
+    fn parse(data: &ApplicationCommandInteractionData) -> ZoneCommandResult<Self> {
+        let subcommand = match data.options.first() {
+            Some(subcommand) => subcommand,
+            None => {
+                return Err(ZoneCommandError::UnknownCommand {
+                    name: data.name.clone(),
+                })
+            }
+        };
+
+        match subcommand.name.as_str() {
+            "list" => Ok(ZoneCommand::List),
+            "add" => {
+                let zone = string_option(subcommand, "zone")?;
+                let zone: Tz = zone
+                    .trim()
+                    .parse()
+                    .map_err(|_| ZoneCommandError::UnknownZone {
+                        zone: String::from(zone.trim()),
+                    })?;
+                Ok(ZoneCommand::Add {
+                    label: label_option(subcommand)?,
+                    zone,
+                })
+            }
+            "remove" => Ok(ZoneCommand::Remove {
+                label: label_option(subcommand)?,
+            }),
+            "move" => {
+                let position = option(subcommand, "position")?
+                    .as_u64()
+                    .ok_or(ZoneCommandError::InvalidOption { name: "position" })?;
+                Ok(ZoneCommand::Move {
+                    label: label_option(subcommand)?,
+                    position: position as usize,
+                })
+            }
+            "reset" => Ok(ZoneCommand::Reset),
+            other => Err(ZoneCommandError::UnknownCommand {
+                name: format!("{} {}", data.name, other),
+            }),
+        }
+    }
+
+    /// Whether the command changes the zones, rather than only showing them.
+    fn is_change(&self) -> bool {
+        !matches!(self, ZoneCommand::List)
+    }
+
+    /// Runs the command for the guild, describing the outcome.
+    async fn run(self, store: &OutputZoneStore, guild_id: GuildId) -> ZoneCommandResult<String> {
+        let outcome = match self {
+            ZoneCommand::List => String::from("Times are converted to:"),
+            ZoneCommand::Add { label, zone } => {
+                store
+                    .update(guild_id, |zones| zones.add(OutputZone::new(&label, zone)))
+                    .await?;
+                format!("Added {}.", label)
+            }
+            ZoneCommand::Remove { label } => {
+                let removed = store.update(guild_id, |zones| zones.remove(&label)).await?;
+                format!("Removed {}.", removed.label())
+            }
+            ZoneCommand::Move { label, position } => {
+                store
+                    .update(guild_id, |zones| zones.move_to(&label, position))
+                    .await?;
+                format!("Moved {} to position {}.", label, position)
+            }
+            ZoneCommand::Reset => {
+                store.reset(guild_id).await?;
+                String::from("Went back to the default zones.")
+            }
+        };
+
+        Ok(format!(
+            "{}\n{}",
+            outcome,
+            describe(&store.zones(guild_id).await)
+        ))
+    }
+}
+
+fn option<'a>(
+    subcommand: &'a ApplicationCommandInteractionDataOption,
+    name: &'static str,
+) -> ZoneCommandResult<&'a Value> {
+    subcommand
+        .options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_ref())
+        .ok_or(ZoneCommandError::MissingOption { name })
+}
+
+fn string_option<'a>(
+    subcommand: &'a ApplicationCommandInteractionDataOption,
+    name: &'static str,
+) -> ZoneCommandResult<&'a str> {
+    option(subcommand, name)?
+        .as_str()
+        .ok_or(ZoneCommandError::InvalidOption { name })
+}
+
+fn label_option(subcommand: &ApplicationCommandInteractionDataOption) -> ZoneCommandResult<String> {
+    let label = string_option(subcommand, "label")?.trim();
+    if label.is_empty() {
+        return Err(ZoneCommandError::InvalidOption { name: "label" });
+    }
+    Ok(String::from(label))
+}
+
+/// Lists the zones in the order they're shown in.
+fn describe(zones: &OutputZones) -> String {
+    let mut description = MessageBuilder::new();
+    for (index, zone) in zones.iter().enumerate() {
+        description
+            .push(format!("{}. ", index + 1))
+            .push_bold_safe(zone.label())
+            .push_line(format!(" ({})", zone.zone().name()));
+    }
+    description.build()
+}
+
+/// The zones command, with a subcommand for each way to manage the zones.
+fn command_definition() -> Value {
+    let label = |description: &str| {
+        json!({
+            "type": 3,
+            "name": "label",
+            "description": description,
+            "required": true,
+        })
+    };
+
+    json!({
+        "name": COMMAND_NAME,
+        "description": "Manage the time zones that times are converted to in this server",
+        "options": [
+            {
+                "type": 1,
+                "name": "list",
+                "description": "List the zones that times are converted to",
+            },
+            {
+                "type": 1,
+                "name": "add",
+                "description": "Add a zone that times are converted to",
+                "options": [
+                    {
+                        "type": 3,
+                        "name": "zone",
+                        "description": "The name of the zone, such as Europe/London",
+                        "required": true,
+                    },
+                    label("The label shown for the zone, such as UK"),
+                ],
+            },
+            {
+                "type": 1,
+                "name": "remove",
+                "description": "Stop converting times to a zone",
+                "options": [label("The label of the zone")],
+            },
+            {
+                "type": 1,
+                "name": "move",
+                "description": "Change where a zone is shown in converted times",
+                "options": [
+                    label("The label of the zone"),
+                    {
+                        "type": 4,
+                        "name": "position",
+                        "description": "The new position of the zone, where 1 is first",
+                        "required": true,
+                    },
+                ],
+            },
+            {
+                "type": 1,
+                "name": "reset",
+                "description": "Go back to the default zones",
+            },
+        ],
+    })
+}
+
+/// Manages the zones that converted times are shown in for each guild, through the zones
+/// slash command. Anyone can list the zones, but only the owner and members with a role
+/// that can manage the server can change them.
+#[derive(Debug)]
+pub struct ZoneCommandHandler {
+    output_zones: Arc<OutputZoneStore>,
+}
+
+impl ZoneCommandHandler {
+    pub fn new(output_zones: Arc<OutputZoneStore>) -> Self {
+        Self { output_zones }
+    }
+
+    async fn is_manager(ctx: &Context, interaction: &Interaction) -> bool {
+        let guild = match interaction.guild_id.to_partial_guild(ctx).await {
+            Ok(guild) => guild,
+            Err(error) => {
+                error!(
+                    "Failed to fetch guild [{}]: {}",
+                    interaction.guild_id, error
+                );
+                return false;
+            }
+        };
+
+        guild.owner_id == interaction.member.user.id
+            || interaction
+                .member
+                .roles
+                .iter()
+                .filter_map(|role_id| guild.roles.get(role_id))
+                .any(|role| role.permissions.administrator() || role.permissions.manage_guild())
+    }
+
+    async fn respond(ctx: &Context, interaction: &Interaction, content: &str) {
+        // Only the member that used the command sees the response.
+        let response = json!({
+            "type": 4,
+            "data": {
+                "content": content,
+                "flags": 64,
+            },
+        });
+
+        if let Err(error) = ctx
+            .http
+            .create_interaction_response(interaction.id.0, &interaction.token, &response)
+            .await
+        {
+            error!("Failed to respond to the zones command: {}", error);
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for ZoneCommandHandler {
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        match ctx
+            .http
+            .create_global_application_command(ready.application.id.0, &command_definition())
+            .await
+        {
+            Ok(_) => info!("Registered the zones command"),
+            Err(error) => error!("Failed to register the zones command: {}", error),
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let data = match (&interaction.kind, &interaction.data) {
+            (InteractionType::ApplicationCommand, Some(data)) if data.name == COMMAND_NAME => data,
+            _ => return,
+        };
+
+        let result = match ZoneCommand::parse(data) {
+            Ok(command) if command.is_change() && !Self::is_manager(&ctx, &interaction).await => {
+                Err(ZoneCommandError::NotAllowed)
+            }
+            Ok(command) => command.run(&self.output_zones, interaction.guild_id).await,
+            Err(error) => Err(error),
+        };
+
+        let content = match result {
+            Ok(content) => content,
+            Err(error) => error.to_string(),
+        };
+        Self::respond(&ctx, &interaction, &content).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono_tz::{America, Asia};
+
+    use super::*;
+
+    fn data(subcommand: Value) -> ApplicationCommandInteractionData {
+        serde_json::from_value(json!({
+            "id": "1",
+            "name": COMMAND_NAME,
+            "options": [subcommand],
+        }))
+        .unwrap()
+    }
+
+    macro_rules! test_parse_data {
+        ($($name:ident{subcommand: $input_subcommand:expr, expected: $expected:expr,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = ZoneCommand::parse(&data($input_subcommand)).unwrap();
+                assert_eq!(actual, $expected);
+            }
+            )*
+        };
+    }
+
+    test_parse_data! {
+        test_parse_list {
+            subcommand: json!({"name": "list"}),
+            expected: ZoneCommand::List,
+        },
+        test_parse_add {
+            subcommand: json!({"name": "add", "options": [
+                {"name": "zone", "value": "Asia/Tokyo"},
+                {"name": "label", "value": " Japan "},
+            ]}),
+            expected: ZoneCommand::Add { label: String::from("Japan"), zone: Asia::Tokyo },
+        },
+        test_parse_remove {
+            subcommand: json!({"name": "remove", "options": [{"name": "label", "value": "UK"}]}),
+            expected: ZoneCommand::Remove { label: String::from("UK") },
+        },
+        test_parse_move {
+            subcommand: json!({"name": "move", "options": [
+                {"name": "label", "value": "US East"},
+                {"name": "position", "value": 1},
+            ]}),
+            expected: ZoneCommand::Move { label: String::from("US East"), position: 1 },
+        },
+        test_parse_reset {
+            subcommand: json!({"name": "reset"}),
+            expected: ZoneCommand::Reset,
+        },
+    }
+
+    macro_rules! test_parse_error_data {
+        ($($name:ident{subcommand: $input_subcommand:expr, expected: $expected:pat,},)*) => {
+            $(
+            #[test]
+            fn $name(){
+                let actual = ZoneCommand::parse(&data($input_subcommand)).unwrap_err();
+                assert!(matches!(actual, $expected), "{:?}", actual);
+            }
+            )*
+        };
+    }
+
+    test_parse_error_data! {
+        test_parse_unknown_subcommand {
+            subcommand: json!({"name": "rename"}),
+            expected: ZoneCommandError::UnknownCommand { .. },
+        },
+        test_parse_unknown_zone {
+            subcommand: json!({"name": "add", "options": [
+                {"name": "zone", "value": "Europe/Nowhere"},
+                {"name": "label", "value": "Nowhere"},
+            ]}),
+            expected: ZoneCommandError::UnknownZone { .. },
+        },
+        test_parse_missing_label {
+            subcommand: json!({"name": "remove"}),
+            expected: ZoneCommandError::MissingOption { name: "label" },
+        },
+        test_parse_blank_label {
+            subcommand: json!({"name": "remove", "options": [{"name": "label", "value": "  "}]}),
+            expected: ZoneCommandError::InvalidOption { name: "label" },
+        },
+        test_parse_negative_position {
+            subcommand: json!({"name": "move", "options": [
+                {"name": "label", "value": "UK"},
+                {"name": "position", "value": -1},
+            ]}),
+            expected: ZoneCommandError::InvalidOption { name: "position" },
+        },
+    }
+
+    #[test]
+    fn test_is_change() {
+        assert!(!ZoneCommand::List.is_change());
+        assert!(ZoneCommand::Reset.is_change());
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let store = OutputZoneStore::load(None).unwrap();
+        let guild_id = GuildId(1);
+
+        let add = ZoneCommand::Add {
+            label: String::from("Japan"),
+            zone: Asia::Tokyo,
+        };
+        add.run(&store, guild_id).await.unwrap();
+
+        let remove = ZoneCommand::Remove {
+            label: String::from("netherlands"),
+        };
+        let content = remove.run(&store, guild_id).await.unwrap();
+        assert_eq!(
+            content,
+            "Removed Netherlands.\n\
+             1. **UK** (Europe/London)\n\
+             2. **US East** (America/New_York)\n\
+             3. **US West** (America/Los_Angeles)\n\
+             4. **Japan** (Asia/Tokyo)\n"
+        );
+
+        let zones = store.zones(guild_id).await;
+        assert_eq!(
+            zones.iter().last(),
+            Some(&OutputZone::new("Japan", Asia::Tokyo))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_error() {
+        let store = OutputZoneStore::load(None).unwrap();
+        let add = ZoneCommand::Add {
+            label: String::from("US East"),
+            zone: America::Detroit,
+        };
+
+        let error = add.run(&store, GuildId(1)).await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "There's already a zone labelled [US East]."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_remove_every_zone() {
+        let store = OutputZoneStore::load(None).unwrap();
+        let guild_id = GuildId(1);
+
+        for label in &["Netherlands", "UK", "US East"] {
+            let remove = ZoneCommand::Remove {
+                label: String::from(*label),
+            };
+            remove.run(&store, guild_id).await.unwrap();
+        }
+
+        let remove = ZoneCommand::Remove {
+            label: String::from("US West"),
+        };
+        let error = remove.run(&store, guild_id).await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Can't remove [US West], a guild needs at least one zone."
+        );
+
+        let content = ZoneCommand::List.run(&store, guild_id).await.unwrap();
+        assert_eq!(
+            content,
+            "Times are converted to:\n\
+             1. **US West** (America/Los_Angeles)\n"
+        );
+    }
+
+    #[test]
+    fn test_command_definition() {
+        let definition = command_definition();
+        let subcommands: Vec<&str> = definition["options"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|subcommand| subcommand["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(subcommands, vec!["list", "add", "remove", "move", "reset"]);
+    }
+}
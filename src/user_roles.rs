@@ -30,7 +30,7 @@ pub enum UserRoleCacheError {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UserRoleCache {
     user_roles: RwLock<HashMap<UserId, Vec<RoleId>>>,
 }